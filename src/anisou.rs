@@ -0,0 +1,152 @@
+/*!
+Contains parsers related to [Anisou](http://www.wwpdb.org/documentation/file-format-content/format33/sect9.html#ANISOU)
+records. The ANISOU records present the anisotropic temperature factors of the atom given
+in the preceding ATOM or HETATM record with the same serial number.
+*/
+use super::{ast::types::*, atom::element_and_charge, primitive::*};
+use nom::{
+    character::complete::{anychar, line_ending},
+    do_parse, map, named, take, take_str,
+};
+
+use std::str;
+
+named!(
+    #[doc=r#"Parses a single ANISOU record. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing [Anisou](../ast/types/struct.Anisou.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD      | DEFINITION                                |
+|----------|--------------|------------|-------------------------------------------|
+| 1 -  6   | Record name  | "ANISOU"   |                                           |
+| 7 - 11   | Integer      | serial     | Atom serial number.                       |
+| 13 - 16  | Atom         | name       | Atom name.                                |
+| 17       | Character    | altLoc     | Alternate location indicator.             |
+| 18 - 20  | Residue name | resName    | Residue name.                             |
+| 22       | Character    | chainID    | Chain identifier.                         |
+| 23 - 26  | Integer      | resSeq     | Residue sequence number.                  |
+| 27       | AChar        | iCode      | Insertion code.                           |
+| 29 - 35  | Integer      | u[0][0]    | U(1,1)                                    |
+| 36 - 42  | Integer      | u[1][1]    | U(2,2)                                    |
+| 43 - 49  | Integer      | u[2][2]    | U(3,3)                                    |
+| 50 - 56  | Integer      | u[0][1]    | U(1,2)                                    |
+| 57 - 63  | Integer      | u[0][2]    | U(1,3)                                    |
+| 64 - 70  | Integer      | u[1][2]    | U(2,3)                                    |
+| 77 - 78  | LString(2)   | element    | Element symbol, right-justified.          |
+| 79 - 80  | LString(2)   | charge     | Charge on the atom.                       |
+"#],
+    pub anisou_record_parser<Record>,
+    do_parse!(
+        anisou
+            >> serial: fivedigit_hybrid36
+            >> take!(1)
+            >> name: map!(take_str!(4), str::trim)
+            >> alt_loc: optional_char
+            >> residue_name: map!(take_str!(3), str::trim)
+            >> take!(1)
+            >> chain_id: anychar
+            >> sequence_number: fourdigit_hybrid36
+            >> insertion_code: optional_char
+            >> take!(1)
+            >> u11: sevendigit_signed_integer
+            >> u22: sevendigit_signed_integer
+            >> u33: sevendigit_signed_integer
+            >> u12: sevendigit_signed_integer
+            >> u13: sevendigit_signed_integer
+            >> u23: sevendigit_signed_integer
+            >> rest: till_line_ending
+            >> line_ending
+            >> ({
+                let (element, charge) = element_and_charge(rest, 6);
+                Record::Anisou(Anisou {
+                    serial,
+                    name: name.to_owned(),
+                    alt_loc,
+                    residue_name: residue_name.to_owned(),
+                    chain_id,
+                    sequence_number,
+                    insertion_code,
+                    u11,
+                    u22,
+                    u33,
+                    u12,
+                    u13,
+                    u23,
+                    element,
+                    charge,
+                })
+            })
+    )
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn anisou() {
+        if let Ok((_, Record::Anisou(res))) = anisou_record_parser(
+            "ANISOU    1  N  AALA A   1     1031   2118   1764   -123   -102    531       N  \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.serial, 1);
+            assert_eq!(res.name, "N");
+            assert_eq!(res.alt_loc, Some('A'));
+            assert_eq!(res.residue_name, "ALA");
+            assert_eq!(res.chain_id, 'A');
+            assert_eq!(res.sequence_number, 1);
+            assert_eq!(
+                [res.u11, res.u22, res.u33, res.u12, res.u13, res.u23],
+                [1031, 2118, 1764, -123, -102, 531]
+            );
+            assert_eq!(res.element, Some("N".to_owned()));
+            assert_eq!(res.charge, None);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn principal_axes() {
+        let anisou = Anisou {
+            u11: 300,
+            u22: 200,
+            u33: 200,
+            u23: 100,
+            ..Default::default()
+        };
+        let axes = anisou.principal_axes();
+        let expected = [0.03, 0.03, 0.01];
+        for (axis, value) in axes.iter().zip(expected.iter()) {
+            assert!((axis.0 - value).abs() < 1e-12);
+        }
+        // smallest displacement is along (0, 1, -1)
+        let direction = axes[2].1;
+        assert!(direction[0].abs() < 1e-9);
+        assert!((direction[1] + direction[2]).abs() < 1e-9);
+        assert!((direction[1].abs() - 0.5f64.sqrt()).abs() < 1e-9);
+        assert!((anisou.anisotropy().unwrap() - 1.0 / 3.0).abs() < 1e-12);
+        let b = 8.0 * std::f64::consts::PI.powi(2) * 0.07 / 3.0;
+        assert!((anisou.equivalent_b() - b).abs() < 1e-12);
+    }
+
+    #[test]
+    fn degenerate_anisotropy() {
+        assert_eq!(Anisou::default().anisotropy(), None);
+        let flat = Anisou {
+            u11: 300,
+            u22: 200,
+            ..Default::default()
+        };
+        assert_eq!(flat.anisotropy(), None);
+        let indefinite = Anisou {
+            u11: 100,
+            u22: 100,
+            u33: 100,
+            u12: 200,
+            ..Default::default()
+        };
+        assert_eq!(indefinite.anisotropy(), None);
+    }
+}
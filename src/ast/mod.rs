@@ -1,2 +1,3 @@
 pub mod pdb_file;
+pub mod structure;
 pub mod types;
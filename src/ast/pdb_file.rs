@@ -1,10 +1,21 @@
 use super::types::*;
-use std::iter::Iterator;
+use crate::{
+    error::{MasterCountError, MasterField, PdbWriteError, SeqresLengthError},
+    remark,
+    symmetry::SpaceGroup,
+    writer::record_lines,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    iter::Iterator,
+};
 
 macro_rules! impl_record_filter {
     ($fn_name : ident -> $match_type: ident -> $ret_type :ident ) => {
-        pub fn $fn_name(&mut self) -> Option<$ret_type> {
+        pub fn $fn_name(&self) -> Option<$ret_type> {
             self.records
+                .as_ref()
                 .iter()
                 .find(|s| match s {
                     Record::$match_type(_) => true,
@@ -12,14 +23,39 @@ macro_rules! impl_record_filter {
                 })
                 .map(|r| match r {
                     Record::$match_type(a) => a.clone(),
-                    _ => $match_type::default(),
+                    _ => $ret_type::default(),
                 })
         }
     };
 }
 
+macro_rules! impl_record_iter {
+    ($fn_name : ident -> $match_type: ident -> $ret_type :ident ) => {
+        pub fn $fn_name(&self) -> impl Iterator<Item = &$ret_type> {
+            self.records.as_ref().iter().filter_map(|s| match s {
+                Record::$match_type(a) => Some(a),
+                _ => None,
+            })
+        }
+    };
+}
+
+/// text lines of every REMARK record with the given number in file order
+fn remark_lines(records: &[Record], number: u32) -> Vec<&str> {
+    records
+        .iter()
+        .filter_map(|r| match r {
+            Record::Remark(remark) if remark.number == number => Some(&remark.lines),
+            _ => None,
+        })
+        .flatten()
+        .map(String::as_str)
+        .collect()
+}
+
 pub struct PdbFile<I> {
     records: I,
+    spans: Vec<Option<SourceSpan>>,
 }
 
 pub trait ToPdbFile {
@@ -30,20 +66,581 @@ pub trait ToPdbFile {
 impl ToPdbFile for Vec<Record> {
     type I = Vec<Record>;
     fn to_pdb_file(self) -> PdbFile<Self::I> {
-        PdbFile { records: self }
+        PdbFile {
+            records: self,
+            spans: Vec::new(),
+        }
     }
 }
 
 impl PdbFile<Vec<Record>> {
-    pub fn header(&mut self) -> PdbHeader<&mut Vec<Record>> {
+    pub(crate) fn with_spans(records: Vec<Record>, spans: Vec<Option<SourceSpan>>) -> Self {
+        PdbFile { records, spans }
+    }
+
+    /// records in file order
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// mutable access to a single record. Source span of the record is dropped,
+    /// so it is rendered again when the file is written
+    pub fn record_mut(&mut self, index: usize) -> Option<&mut Record> {
+        if let Some(span) = self.spans.get_mut(index) {
+            *span = None;
+        }
+        self.records.get_mut(index)
+    }
+
+    /// source span of a record. Only available for files parsed with
+    /// [parse_lossless](fn.parse_lossless.html) and records which are not edited
+    pub fn span(&self, index: usize) -> Option<&SourceSpan> {
+        self.spans.get(index).and_then(Option::as_ref)
+    }
+
+    pub fn header(&self) -> PdbHeader<&[Record]> {
         PdbHeader {
-            records: &mut self.records,
+            records: &self.records,
         }
     }
 
-    pub fn primary(&mut self) -> PrimaryStructure<&mut Vec<Record>> {
+    pub fn primary(&self) -> PrimaryStructure<&[Record]> {
         PrimaryStructure {
-            records: &mut self.records,
+            records: &self.records,
+        }
+    }
+
+    pub fn coordinates(&self) -> Coordinates<&[Record]> {
+        Coordinates {
+            records: &self.records,
+        }
+    }
+
+    pub fn secondary_structure(&self) -> SecondaryStructure<&[Record]> {
+        SecondaryStructure {
+            records: &self.records,
+        }
+    }
+
+    pub fn connectivity(&self) -> Connectivity<&[Record]> {
+        Connectivity {
+            records: &self.records,
+        }
+    }
+
+    pub fn crystal(&self) -> Crystal<&[Record]> {
+        Crystal {
+            records: &self.records,
+        }
+    }
+
+    pub fn sites(&self) -> Sites<&[Record]> {
+        Sites {
+            records: &self.records,
+        }
+    }
+
+    pub fn heterogens(&self) -> Heterogens<&[Record]> {
+        Heterogens {
+            records: &self.records,
+        }
+    }
+
+    /// text lines of REMARK records keyed by remark number. Lines of
+    /// records sharing a number are concatenated in file order
+    pub fn remarks(&self) -> BTreeMap<u32, Vec<&str>> {
+        let mut remarks = BTreeMap::new();
+        for record in self.records.iter() {
+            if let Record::Remark(remark) = record {
+                remarks
+                    .entry(remark.number)
+                    .or_insert_with(Vec::new)
+                    .extend(remark.lines.iter().map(String::as_str));
+            }
+        }
+        remarks
+    }
+
+    /// MASTER record of the entry
+    pub fn master(&self) -> Option<&Master> {
+        self.records.iter().find_map(|r| match r {
+            Record::Master(m) => Some(m),
+            _ => None,
+        })
+    }
+
+    /// Counts the records of the entry in the layout of the MASTER record. Records merged
+    /// from several lines are counted in the number of lines they were read from, or
+    /// written with when built in code.
+    /// Turns are not counted since TURN records are no longer used
+    // usize::div_ceil needs Rust 1.73
+    #[allow(clippy::manual_div_ceil)]
+    pub fn counted_master(&self) -> Master {
+        let lines = |source_lines: u32, items: usize, per_line: usize| match source_lines {
+            0 => ((items + per_line - 1) / per_line).max(1) as u32,
+            source_lines => source_lines,
+        };
+        let mut master = Master::default();
+        for record in self.records.iter() {
+            match record {
+                Record::Remark(r) => master.num_remark += r.lines.len() as u32,
+                Record::Het(_) => master.num_het += 1,
+                Record::Helix(_) => master.num_helix += 1,
+                Record::Sheet(_) => master.num_sheet += 1,
+                Record::Site(s) => master.num_site += lines(s.source_lines, s.residues.len(), 4),
+                Record::Origx(_) | Record::Scale(_) | Record::Mtrix(_) => master.num_xform += 3,
+                Record::Atom(_) | Record::Hetatm(_) => master.num_coord += 1,
+                Record::Ter(_) => master.num_ter += 1,
+                Record::Conect(c) => master.num_conect += lines(c.source_lines, c.bonded.len(), 4),
+                Record::Seqres(s) => master.num_seq += lines(s.source_lines, s.residues.len(), 13),
+                _ => {}
+            }
+        }
+        master
+    }
+
+    /// Compares the counts given in the MASTER record with the records of the entry and
+    /// returns every count which differs. Entries without a MASTER record are not checked
+    pub fn validate_master(&self) -> Result<(), Vec<MasterCountError>> {
+        let master = match self.master() {
+            Some(master) => master,
+            None => return Ok(()),
+        };
+        let counted = self.counted_master();
+        let errors = [
+            (MasterField::Remark, master.num_remark, counted.num_remark),
+            (MasterField::Het, master.num_het, counted.num_het),
+            (MasterField::Helix, master.num_helix, counted.num_helix),
+            (MasterField::Sheet, master.num_sheet, counted.num_sheet),
+            (MasterField::Site, master.num_site, counted.num_site),
+            (MasterField::Xform, master.num_xform, counted.num_xform),
+            (MasterField::Coord, master.num_coord, counted.num_coord),
+            (MasterField::Ter, master.num_ter, counted.num_ter),
+            (MasterField::Conect, master.num_conect, counted.num_conect),
+            (MasterField::Seq, master.num_seq, counted.num_seq),
+        ]
+        .iter()
+        .filter(|(_, master, counted)| master != counted)
+        .map(|&(field, master, counted)| MasterCountError {
+            field,
+            master,
+            counted,
+        })
+        .collect::<Vec<_>>();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// biological assemblies given in REMARK 350
+    pub fn assemblies(&self) -> Vec<BiologicalAssembly> {
+        remark::biological_assemblies(&remark_lines(&self.records, 350))
+    }
+
+    /// Generates the biological assembly with the given number. Every operator of the
+    /// assembly is applied to the ATOM and HETATM records of its chains in the first
+    /// model and the result is returned as a separate model, numbered from 1.
+    pub fn biological_assembly(&self, number: u32) -> Option<PdbFile<Vec<Record>>> {
+        let assembly = self.assemblies().into_iter().find(|a| a.number == number)?;
+        let model = self.models().next()?;
+        let mut records = Vec::new();
+        for (serial, operator) in assembly.operators.iter().enumerate() {
+            records.push(Record::Model(Model {
+                serial: serial as u32 + 1,
+            }));
+            for record in model.records {
+                let transformed = |atom: &Atom| {
+                    let [x, y, z] = operator.matrix.apply([atom.x, atom.y, atom.z]);
+                    Atom {
+                        x,
+                        y,
+                        z,
+                        ..atom.clone()
+                    }
+                };
+                match record {
+                    Record::Atom(atom) if operator.chains.contains(&atom.chain_id) => {
+                        records.push(Record::Atom(transformed(atom)))
+                    }
+                    Record::Hetatm(atom) if operator.chains.contains(&atom.chain_id) => {
+                        records.push(Record::Hetatm(transformed(atom)))
+                    }
+                    _ => {}
+                }
+            }
+            records.push(Record::Endmdl);
+        }
+        Some(records.to_pdb_file())
+    }
+
+    /// Generates the non-crystallographic symmetry copies missing from the coordinate
+    /// section. Every MTRIXn operator whose copy is not given in the entry is applied to
+    /// the ATOM and HETATM records of the first model belonging to one of `chains`, so
+    /// that waters and ligands outside the asymmetric unit can be left out. `chain_id`
+    /// maps the operator serial number and the original chain identifier to the chain
+    /// identifier of the copy. Atoms of the copies are numbered after the last atom of
+    /// the entry.
+    pub fn ncs_copies<F>(&self, chains: &[char], mut chain_id: F) -> Vec<Record>
+    where
+        F: FnMut(u32, char) -> char,
+    {
+        let model = match self.models().next() {
+            Some(model) => model,
+            None => return Vec::new(),
+        };
+        let mut serial = self
+            .coordinates()
+            .all()
+            .map(|a| a.serial)
+            .max()
+            .unwrap_or_default();
+        let mut records = Vec::new();
+        for operator in self.crystal().ncs_operators().filter(|m| !m.given) {
+            let mut transformed = |atom: &Atom| {
+                let [x, y, z] = operator.matrix.apply([atom.x, atom.y, atom.z]);
+                serial += 1;
+                Atom {
+                    serial,
+                    chain_id: chain_id(operator.serial, atom.chain_id),
+                    x,
+                    y,
+                    z,
+                    ..atom.clone()
+                }
+            };
+            for record in model.records {
+                match record {
+                    Record::Atom(atom) if chains.contains(&atom.chain_id) => {
+                        records.push(Record::Atom(transformed(atom)))
+                    }
+                    Record::Hetatm(atom) if chains.contains(&atom.chain_id) => {
+                        records.push(Record::Hetatm(transformed(atom)))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        records
+    }
+
+    /// copy of the entry with the [NCS copies](#method.ncs_copies) inserted after the
+    /// last coordinate record of the first model
+    pub fn expand_ncs<F>(&self, chains: &[char], chain_id: F) -> PdbFile<Vec<Record>>
+    where
+        F: FnMut(u32, char) -> char,
+    {
+        let copies = self.ncs_copies(chains, chain_id);
+        let first_model_end = self
+            .records
+            .iter()
+            .position(|r| matches!(r, Record::Endmdl))
+            .unwrap_or(self.records.len());
+        let position = self.records[..first_model_end]
+            .iter()
+            .rposition(|r| matches!(r, Record::Atom(_) | Record::Hetatm(_)))
+            .map_or(first_model_end, |p| p + 1);
+        let mut records = self.records[..position].to_vec();
+        records.extend(copies);
+        records.extend_from_slice(&self.records[position..]);
+        records.to_pdb_file()
+    }
+
+    /// ATOM and HETATM records of the first model transformed by `matrix`
+    pub fn transformed(&self, matrix: &Matrix3x4) -> Vec<Record> {
+        let model = match self.models().next() {
+            Some(model) => model,
+            None => return Vec::new(),
+        };
+        let transformed = |atom: &Atom| {
+            let [x, y, z] = matrix.apply([atom.x, atom.y, atom.z]);
+            Atom {
+                x,
+                y,
+                z,
+                ..atom.clone()
+            }
+        };
+        model
+            .records
+            .iter()
+            .filter_map(|record| match record {
+                Record::Atom(atom) => Some(Record::Atom(transformed(atom))),
+                Record::Hetatm(atom) => Some(Record::Hetatm(transformed(atom))),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Generates the unit cell. Every symmetry operator of the space group is applied to
+    /// the ATOM and HETATM records of the first model and the copy is moved by whole unit
+    /// cells so that its centre lies inside the cell. Copies are returned as separate
+    /// models, numbered from 1 in the order of the operators.
+    pub fn unit_cell(&self) -> Option<PdbFile<Vec<Record>>> {
+        let crystal = self.crystal();
+        let group = crystal.space_group()?;
+        let fractionalization = crystal.fractionalization()?;
+        let orthogonalization = fractionalization.inverse()?;
+        let centre = fractionalization.apply(centroid(&self.first_model_positions())?);
+        let mut records = Vec::new();
+        for (serial, operator) in group.operators().iter().enumerate() {
+            let image = operator.apply(centre);
+            let cell = [
+                -image[0].floor() as i32,
+                -image[1].floor() as i32,
+                -image[2].floor() as i32,
+            ];
+            let matrix =
+                orthogonal_operator(operator, cell, &fractionalization, &orthogonalization);
+            records.push(Record::Model(Model {
+                serial: serial as u32 + 1,
+            }));
+            records.extend(self.transformed(&matrix));
+            records.push(Record::Endmdl);
+        }
+        Some(records.to_pdb_file())
+    }
+
+    /// Finds the crystallographic symmetry copies having an atom within `cutoff` angstroms
+    /// of an atom of the first model. Copies generated by every operator of the space group
+    /// and shifted by up to one unit cell from the original molecule are considered. The
+    /// original molecule itself is not included.
+    pub fn symmetry_mates(&self, cutoff: f64) -> Vec<SymmetryMate> {
+        let crystal = self.crystal();
+        let positions = self.first_model_positions();
+        let (group, fractionalization, orthogonalization, centre) = match (
+            crystal.space_group(),
+            crystal.fractionalization(),
+            crystal.fractionalization().and_then(|f| f.inverse()),
+            centroid(&positions),
+        ) {
+            (Some(g), Some(f), Some(o), Some(c)) if cutoff > 0.0 => (g, f, o, f.apply(c)),
+            _ => return Vec::new(),
+        };
+        let grid = PositionGrid::new(&positions, cutoff);
+        let mut mates = Vec::new();
+        for (index, operator) in group.operators().iter().enumerate() {
+            let image = operator.apply(centre);
+            let base = [
+                (centre[0] - image[0]).round() as i32,
+                (centre[1] - image[1]).round() as i32,
+                (centre[2] - image[2]).round() as i32,
+            ];
+            for i in -1..=1 {
+                for j in -1..=1 {
+                    for k in -1..=1 {
+                        let translation = [base[0] + i, base[1] + j, base[2] + k];
+                        if index == 0 && translation == [0; 3] {
+                            continue;
+                        }
+                        let matrix = orthogonal_operator(
+                            operator,
+                            translation,
+                            &fractionalization,
+                            &orthogonalization,
+                        );
+                        if positions
+                            .iter()
+                            .any(|p| grid.has_neighbour(matrix.apply(*p)))
+                        {
+                            mates.push(SymmetryMate {
+                                operator: index,
+                                translation,
+                                matrix,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        mates
+    }
+
+    fn first_model_positions(&self) -> Vec<[f64; 3]> {
+        self.models()
+            .next()
+            .map(|model| {
+                model
+                    .records
+                    .iter()
+                    .filter_map(|record| match record {
+                        Record::Atom(atom) | Record::Hetatm(atom) => Some([atom.x, atom.y, atom.z]),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// iterates models in file order. Coordinate records between a MODEL
+    /// and its ENDMDL record are grouped under the serial number of the
+    /// model. Entries without MODEL records yield a single model numbered 1.
+    pub fn models(&self) -> impl Iterator<Item = CoordinateModel<'_>> {
+        let mut models = Vec::new();
+        let mut current: Option<(u32, usize)> = None;
+        for (index, record) in self.records.iter().enumerate() {
+            match record {
+                Record::Model(Model { serial }) => current = Some((*serial, index + 1)),
+                Record::Endmdl => {
+                    if let Some((serial, start)) = current.take() {
+                        models.push(CoordinateModel {
+                            serial,
+                            records: &self.records[start..index],
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some((serial, start)) = current {
+            models.push(CoordinateModel {
+                serial,
+                records: &self.records[start..],
+            });
+        }
+        if models.is_empty()
+            && self
+                .records
+                .iter()
+                .any(|r| matches!(r, Record::Atom(_) | Record::Hetatm(_)))
+        {
+            models.push(CoordinateModel {
+                serial: 1,
+                records: &self.records[..],
+            });
+        }
+        models.into_iter()
+    }
+
+    /// writes every record in file order. Records having a source span are
+    /// written with their original text, others as 80 column PDB lines.
+    /// A number which does not fit into its columns is reported as an
+    /// `InvalidData` error wrapping a [PdbWriteError](../../error/struct.PdbWriteError.html)
+    pub fn write<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        let text = self
+            .to_pdb_string()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        writer.write_all(text.as_bytes())
+    }
+
+    /// serializes the records to PDB text. Fails if a serial or sequence number
+    /// does not fit into its columns, even as a hybrid-36 number
+    pub fn to_pdb_string(&self) -> Result<String, PdbWriteError> {
+        let mut text = String::new();
+        for (index, record) in self.records.iter().enumerate() {
+            match self.span(index) {
+                Some(span) => text.push_str(&span.text),
+                None => {
+                    for line in record_lines(record)? {
+                        text.push_str(&line);
+                        text.push('\n');
+                    }
+                }
+            }
+        }
+        Ok(text)
+    }
+}
+
+fn centroid(positions: &[[f64; 3]]) -> Option<[f64; 3]> {
+    if positions.is_empty() {
+        return None;
+    }
+    let mut sum = [0.0; 3];
+    for position in positions {
+        for (s, p) in sum.iter_mut().zip(position.iter()) {
+            *s += p;
+        }
+    }
+    let count = positions.len() as f64;
+    Some([sum[0] / count, sum[1] / count, sum[2] / count])
+}
+
+/// fractional symmetry operator followed by a unit cell translation, given in orthogonal
+/// coordinates
+fn orthogonal_operator(
+    operator: &Matrix3x4,
+    translation: [i32; 3],
+    fractionalization: &Matrix3x4,
+    orthogonalization: &Matrix3x4,
+) -> Matrix3x4 {
+    let mut operator = *operator;
+    for (row, t) in operator.rows.iter_mut().zip(translation.iter()) {
+        row[3] += f64::from(*t);
+    }
+    orthogonalization.multiply(&operator.multiply(fractionalization))
+}
+
+/// positions bucketed into cubic cells with an edge of the search distance
+struct PositionGrid<'a> {
+    distance: f64,
+    cells: HashMap<[i64; 3], Vec<&'a [f64; 3]>>,
+    min: [f64; 3],
+    max: [f64; 3],
+}
+
+impl<'a> PositionGrid<'a> {
+    fn new(positions: &'a [[f64; 3]], distance: f64) -> Self {
+        let mut grid = PositionGrid {
+            distance,
+            cells: HashMap::new(),
+            min: [f64::INFINITY; 3],
+            max: [f64::NEG_INFINITY; 3],
+        };
+        for position in positions {
+            for (axis, value) in position.iter().enumerate() {
+                grid.min[axis] = grid.min[axis].min(value - distance);
+                grid.max[axis] = grid.max[axis].max(value + distance);
+            }
+            grid.cells
+                .entry(grid.cell(position))
+                .or_default()
+                .push(position);
+        }
+        grid
+    }
+
+    fn cell(&self, position: &[f64; 3]) -> [i64; 3] {
+        [
+            (position[0] / self.distance).floor() as i64,
+            (position[1] / self.distance).floor() as i64,
+            (position[2] / self.distance).floor() as i64,
+        ]
+    }
+
+    /// whether any position lies within the search distance of `point`
+    fn has_neighbour(&self, point: [f64; 3]) -> bool {
+        if (0..3).any(|axis| point[axis] < self.min[axis] || point[axis] > self.max[axis]) {
+            return false;
+        }
+        let [x, y, z] = self.cell(&point);
+        let squared = self.distance * self.distance;
+        (x - 1..=x + 1).any(|i| {
+            (y - 1..=y + 1).any(|j| {
+                (z - 1..=z + 1).any(|k| {
+                    self.cells.get(&[i, j, k]).into_iter().flatten().any(|p| {
+                        (0..3)
+                            .map(|axis| (p[axis] - point[axis]).powi(2))
+                            .sum::<f64>()
+                            <= squared
+                    })
+                })
+            })
+        })
+    }
+}
+
+/// coordinate records of a single model
+pub struct CoordinateModel<'a> {
+    pub serial: u32,
+    pub(crate) records: &'a [Record],
+}
+
+impl<'a> CoordinateModel<'a> {
+    pub fn coordinates(&self) -> Coordinates<&'a [Record]> {
+        Coordinates {
+            records: self.records,
         }
     }
 }
@@ -52,7 +649,7 @@ pub struct PdbHeader<I> {
     records: I,
 }
 
-impl<'a> PdbHeader<&'a mut Vec<Record>> {
+impl<I: AsRef<[Record]>> PdbHeader<I> {
     impl_record_filter!(nummdl -> Nummdl -> Nummdl);
     impl_record_filter!(obslte -> Obslte -> Obslte);
     impl_record_filter!(caveat -> Caveat -> Caveat);
@@ -67,9 +664,19 @@ impl<'a> PdbHeader<&'a mut Vec<Record>> {
     impl_record_filter!(keywds -> Keywds -> Keywds);
     impl_record_filter!(expdta ->  Experimental -> Experimental);
 
-    pub fn journal(&'a mut self) -> PdbJournal<&'a mut Vec<Record>> {
+    /// resolution given in REMARK 2
+    pub fn resolution(&self) -> Option<Resolution> {
+        remark::resolution(&remark_lines(self.records.as_ref(), 2))
+    }
+
+    /// refinement statistics given in REMARK 3
+    pub fn refinement(&self) -> Option<Refinement> {
+        remark::refinement(&remark_lines(self.records.as_ref(), 3))
+    }
+
+    pub fn journal(&self) -> PdbJournal<&[Record]> {
         PdbJournal {
-            records: self.records,
+            records: self.records.as_ref(),
         }
     }
 }
@@ -77,7 +684,7 @@ pub struct PdbJournal<I> {
     records: I,
 }
 
-impl<'a> PdbJournal<&'a mut Vec<Record>> {
+impl<I: AsRef<[Record]>> PdbJournal<I> {
     impl_record_filter!(authors -> JournalAuthors -> JournalAuthors);
     impl_record_filter!(title -> JournalTitle -> JournalTitle);
     impl_record_filter!(editors -> JournalEditors -> JournalEditors);
@@ -92,13 +699,341 @@ pub struct PrimaryStructure<I> {
     records: I,
 }
 
-impl<'a> PrimaryStructure<&'a mut Vec<Record>> {
+impl<I: AsRef<[Record]>> PrimaryStructure<I> {
     impl_record_filter!(dbreference -> Dbref -> Dbref);
     impl_record_filter!(conflicts -> Seqadv -> Seqadv);
     impl_record_filter!(residues -> Seqres -> Seqres);
+    impl_record_iter!(dbreferences -> Dbref -> Dbref);
+    impl_record_iter!(conflicts_all -> Seqadv -> Seqadv);
+    impl_record_iter!(residues_all -> Seqres -> Seqres);
+    impl_record_iter!(modifications -> Modres -> Modres);
+
+    /// full residue lists of SEQRES records keyed by chain identifier. Blank chain
+    /// identifiers are keyed by `' '`. Fails if the number of residues of a chain
+    /// differs from its numRes field.
+    pub fn sequences(&self) -> Result<BTreeMap<char, Vec<String>>, SeqresLengthError> {
+        let mut sequences: BTreeMap<char, Vec<String>> = BTreeMap::new();
+        let mut num_res: BTreeMap<char, (u32, Option<u32>)> = BTreeMap::new();
+        for record in self.records.as_ref().iter() {
+            if let Record::Seqres(seqres) = record {
+                let chain_id = seqres.chain_id.unwrap_or(' ');
+                let (first, conflicting) =
+                    num_res.entry(chain_id).or_insert((seqres.num_res, None));
+                if *first != seqres.num_res {
+                    *conflicting = conflicting.or(Some(seqres.num_res));
+                }
+                *conflicting = conflicting.or(seqres.conflicting_num_res);
+                sequences
+                    .entry(chain_id)
+                    .or_default()
+                    .extend(seqres.residues.iter().cloned());
+            }
+        }
+        for (chain_id, residues) in sequences.iter() {
+            let (first, conflicting) = num_res[chain_id];
+            if conflicting.is_some() || residues.len() != first as usize {
+                return Err(SeqresLengthError {
+                    chain_id: *chain_id,
+                    num_res: first,
+                    residues: residues.len(),
+                    conflicting_num_res: conflicting,
+                });
+            }
+        }
+        Ok(sequences)
+    }
+
+    /// MODRES records keyed by chain identifier, residue sequence number and insertion code
+    pub fn modified_residues(&self) -> BTreeMap<(char, i32, Option<char>), &Modres> {
+        self.modifications()
+            .map(|m| ((m.chain_id, m.sequence_number, m.insertion_code), m))
+            .collect()
+    }
+
+    /// MODRES record of the residue at the given position
+    pub fn modified_residue(
+        &self,
+        chain_id: char,
+        sequence_number: i32,
+        insertion_code: Option<char>,
+    ) -> Option<&Modres> {
+        self.modifications().find(|m| {
+            m.chain_id == chain_id
+                && m.sequence_number == sequence_number
+                && m.insertion_code == insertion_code
+        })
+    }
+
+    /// standard residue name a modified residue is derived from, such as MET for MSE.
+    /// Returns `None` if no MODRES record names the residue
+    pub fn standard_residue_name(&self, residue_name: &str) -> Option<&str> {
+        self.modifications()
+            .find(|m| m.residue_name == residue_name)
+            .map(|m| m.standart_residue_name.as_str())
+    }
+
+    /// residue lists of [sequences](#method.sequences) with modified residues
+    /// replaced by their standard residue names
+    pub fn standard_sequences(&self) -> Result<BTreeMap<char, Vec<String>>, SeqresLengthError> {
+        let mut sequences = self.sequences()?;
+        for residue in sequences.values_mut().flatten() {
+            if let Some(standard) = self.standard_residue_name(residue) {
+                *residue = standard.to_owned();
+            }
+        }
+        Ok(sequences)
+    }
+
+    /// residues without coordinates listed in REMARK 465
+    pub fn missing_residues(&self) -> BTreeMap<ResidueLocation, MissingResidue> {
+        remark::missing_residues(&remark_lines(self.records.as_ref(), 465))
+    }
+
+    /// residues having atoms without coordinates listed in REMARK 470
+    pub fn missing_atoms(&self) -> BTreeMap<ResidueLocation, MissingAtoms> {
+        remark::missing_atoms(&remark_lines(self.records.as_ref(), 470))
+    }
+}
+
+/// secondary structure element a residue belongs to
+#[derive(Debug, Clone, Copy)]
+pub enum SecondaryStructureElement<'a> {
+    Helix(&'a Helix),
+    Strand(&'a Sheet),
+}
+
+/// whether the residue at the given position lies between `start` and `end` inclusive
+fn spans_residue(
+    start: &ResidueReference,
+    end: &ResidueReference,
+    chain_id: char,
+    sequence_number: i32,
+    insertion_code: Option<char>,
+) -> bool {
+    let position = (sequence_number, insertion_code);
+    start.chain_id == chain_id
+        && (start.sequence_number, start.insertion_code) <= position
+        && position <= (end.sequence_number, end.insertion_code)
+}
+
+pub struct SecondaryStructure<I> {
+    records: I,
+}
+
+impl<I: AsRef<[Record]>> SecondaryStructure<I> {
+    impl_record_iter!(helices -> Helix -> Helix);
+    impl_record_iter!(strands -> Sheet -> Sheet);
+
+    /// strands of the sheet with the given identifier
+    pub fn sheet<'a>(&'a self, sheet_id: &'a str) -> impl Iterator<Item = &'a Sheet> {
+        self.strands().filter(move |s| s.sheet_id == sheet_id)
+    }
+
+    /// helix or strand containing the residue at the given position. Helices take
+    /// precedence if a residue is assigned to both
+    pub fn element_at(
+        &self,
+        chain_id: char,
+        sequence_number: i32,
+        insertion_code: Option<char>,
+    ) -> Option<SecondaryStructureElement<'_>> {
+        self.helices()
+            .find(|h| spans_residue(&h.start, &h.end, chain_id, sequence_number, insertion_code))
+            .map(SecondaryStructureElement::Helix)
+            .or_else(|| {
+                self.strands()
+                    .find(|s| {
+                        spans_residue(&s.start, &s.end, chain_id, sequence_number, insertion_code)
+                    })
+                    .map(SecondaryStructureElement::Strand)
+            })
+    }
+}
+
+pub struct Connectivity<I> {
+    records: I,
+}
+
+impl<I: AsRef<[Record]>> Connectivity<I> {
+    impl_record_iter!(disulfides -> Ssbond -> Ssbond);
+    impl_record_iter!(links -> Link -> Link);
+    impl_record_iter!(cis_peptides -> Cispep -> Cispep);
+    impl_record_iter!(conects -> Conect -> Conect);
+
+    /// serial numbers of the atoms bonded to the given atom in CONECT records.
+    /// Records of the atom are merged even if they are not consecutive
+    pub fn bonded_to(&self, serial: u32) -> Vec<u32> {
+        let mut bonded: Vec<u32> = Vec::new();
+        for conect in self.conects().filter(|c| c.serial == serial) {
+            for atom in conect.bonded.iter() {
+                if !bonded.contains(atom) {
+                    bonded.push(*atom);
+                }
+            }
+        }
+        bonded
+    }
+
+    /// LINK records involving an atom of the residue at the given position
+    pub fn links_of(
+        &self,
+        chain_id: char,
+        sequence_number: i32,
+        insertion_code: Option<char>,
+    ) -> impl Iterator<Item = &Link> {
+        self.links().filter(move |l| {
+            [&l.atom1, &l.atom2].iter().any(|a| {
+                a.chain_id == chain_id
+                    && a.sequence_number == sequence_number
+                    && a.insertion_code == insertion_code
+            })
+        })
+    }
+}
+
+pub struct Crystal<I> {
+    records: I,
+}
+
+impl<I: AsRef<[Record]>> Crystal<I> {
+    impl_record_filter!(cryst1 -> Cryst1 -> Cryst1);
+    impl_record_filter!(origx -> Origx -> Matrix3x4);
+    impl_record_filter!(scale -> Scale -> Matrix3x4);
+    impl_record_iter!(ncs_operators -> Mtrix -> Mtrix);
+
+    /// transformation from orthogonal to fractional coordinates. Uses SCALEn records
+    /// if present, otherwise the standard orientation of the CRYST1 unit cell
+    pub fn fractionalization(&self) -> Option<Matrix3x4> {
+        self.scale()
+            .or_else(|| self.cryst1().map(|c| c.fractionalization_matrix()))
+    }
+
+    /// space group of the CRYST1 record
+    pub fn space_group(&self) -> Option<SpaceGroup> {
+        self.cryst1()
+            .and_then(|c| SpaceGroup::from_symbol(&c.space_group))
+    }
+
+    /// symmetry operators of the space group in orthogonal coordinates
+    pub fn symmetry_operators(&self) -> Vec<Matrix3x4> {
+        match (
+            self.space_group(),
+            self.fractionalization(),
+            self.fractionalization().and_then(|f| f.inverse()),
+        ) {
+            (Some(group), Some(fractionalization), Some(orthogonalization)) => group
+                .operators()
+                .iter()
+                .map(|o| orthogonal_operator(o, [0; 3], &fractionalization, &orthogonalization))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// fractional coordinates of an orthogonal point
+    pub fn to_fractional(&self, point: [f64; 3]) -> Option<[f64; 3]> {
+        self.fractionalization().map(|m| m.apply(point))
+    }
+
+    /// orthogonal coordinates of a fractional point
+    pub fn to_orthogonal(&self, fractional: [f64; 3]) -> Option<[f64; 3]> {
+        self.fractionalization()
+            .and_then(|m| m.inverse())
+            .map(|m| m.apply(fractional))
+    }
+}
+
+pub struct Sites<I> {
+    records: I,
+}
+
+impl<I: AsRef<[Record]>> Sites<I> {
+    impl_record_iter!(all -> Site -> Site);
+
+    /// SITE record with the given site identifier
+    pub fn site(&self, site_id: &str) -> Option<&Site> {
+        self.all().find(|s| s.site_id == site_id)
+    }
+
+    /// residues of the site with the given identifier. Empty if there is no such site
+    pub fn residues(&self, site_id: &str) -> &[ResidueReference] {
+        self.site(site_id)
+            .map(|s| s.residues.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// site descriptions given in REMARK 800 keyed by site identifier
+    pub fn descriptions(&self) -> BTreeMap<String, SiteDescription> {
+        remark::site_descriptions(&remark_lines(self.records.as_ref(), 800))
+    }
+
+    /// sites paired with their REMARK 800 descriptions in file order
+    pub fn described(&self) -> Vec<(&Site, Option<SiteDescription>)> {
+        let mut descriptions = self.descriptions();
+        self.all()
+            .map(|s| (s, descriptions.remove(&s.site_id)))
+            .collect()
+    }
+}
+
+pub struct Heterogens<I> {
+    records: I,
+}
+
+impl<I: AsRef<[Record]>> Heterogens<I> {
+    impl_record_iter!(hets -> Het -> Het);
+    impl_record_iter!(names -> Hetnam -> Hetnam);
+    impl_record_iter!(synonyms -> Hetsyn -> Hetsyn);
+    impl_record_iter!(formulas -> Formul -> Formul);
+
+    /// HET records of all instances of the given het group
+    pub fn instances<'a>(&'a self, het_id: &'a str) -> impl Iterator<Item = &'a Het> {
+        self.hets().filter(move |h| h.het_id == het_id)
+    }
+
+    /// chemical name of the given het group
+    pub fn name(&self, het_id: &str) -> Option<&str> {
+        self.names()
+            .find(|h| h.het_id == het_id)
+            .map(|h| h.name.as_str())
+    }
+
+    /// synonyms of the given het group. Empty if the group has no HETSYN record
+    pub fn synonyms_of(&self, het_id: &str) -> Vec<&str> {
+        self.synonyms()
+            .filter(|h| h.het_id == het_id)
+            .flat_map(|h| h.synonyms.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// FORMUL record of the given het group
+    pub fn formula(&self, het_id: &str) -> Option<&Formul> {
+        self.formulas().find(|f| f.het_id == het_id)
+    }
+}
+
+pub struct Coordinates<I> {
+    records: I,
+}
+
+impl<I: AsRef<[Record]>> Coordinates<I> {
+    impl_record_iter!(atoms -> Atom -> Atom);
+    impl_record_iter!(hetatms -> Hetatm -> Atom);
+    impl_record_iter!(anisous -> Anisou -> Anisou);
+    impl_record_iter!(terminations -> Ter -> Ter);
+
+    /// ATOM and HETATM records in the order they appear in the file
+    pub fn all(&self) -> impl Iterator<Item = &Atom> {
+        self.records.as_ref().iter().filter_map(|s| match s {
+            Record::Atom(a) | Record::Hetatm(a) => Some(a),
+            _ => None,
+        })
+    }
 }
 #[cfg(test)]
 mod test {
+    use crate::record::test::{get_test_file_path, read_file};
+
     #[test]
     fn test_pdb_file_constructor() {
         use super::{super::types::Record, *};
@@ -108,9 +1043,351 @@ mod test {
             }),
             Record::Nummdl(Nummdl { num: 1 }),
         ];
-        let mut parsed_pdb = a.to_pdb_file();
+        let parsed_pdb = a.to_pdb_file();
         let tit = parsed_pdb.header().title();
         assert_eq!(tit.unwrap().title, "a".to_owned());
         assert_eq!(parsed_pdb.header().nummdl().unwrap().num, 1);
     }
+
+    #[test]
+    fn iterator_accessors() {
+        use super::{super::types::Record, *};
+        let parsed_pdb = vec![
+            Record::Seqadv(Seqadv {
+                sequence_number: 1,
+                ..Seqadv::default()
+            }),
+            Record::Modres(Modres::default()),
+            Record::Seqadv(Seqadv {
+                sequence_number: 2,
+                ..Seqadv::default()
+            }),
+        ]
+        .to_pdb_file();
+        let primary = parsed_pdb.primary();
+        let header = parsed_pdb.header();
+        assert_eq!(
+            primary
+                .conflicts_all()
+                .map(|c| c.sequence_number)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(primary.modifications().count(), 1);
+        assert_eq!(primary.dbreferences().count(), 0);
+        assert!(header.title().is_none());
+    }
+
+    #[test]
+    fn ncs_expansion() {
+        let (_, pdb) = crate::parse(
+            r#"MTRIX1   1  1.000000  0.000000  0.000000        0.00000    1
+MTRIX2   1  0.000000  1.000000  0.000000        0.00000    1
+MTRIX3   1  0.000000  0.000000  1.000000        0.00000    1
+MTRIX1   2 -1.000000  0.000000  0.000000       10.00000
+MTRIX2   2  0.000000 -1.000000  0.000000        0.00000
+MTRIX3   2  0.000000  0.000000  1.000000        0.00000
+ATOM      1  N   SER A   1      16.250  42.032  25.571  1.00 20.91           N
+HETATM    2  C1  NAG A 301      -5.012  21.230  -1.005  1.00 12.02           C
+HETATM    3  O   HOH W   1       1.000   2.000   3.000  1.00 12.02           O
+"#,
+        )
+        .unwrap();
+        let copies = pdb.ncs_copies(
+            &['A'],
+            |serial, chain| if serial == 2 { 'B' } else { chain },
+        );
+        assert_eq!(copies.len(), 2);
+        assert!(pdb.ncs_copies(&[], |_, chain| chain).is_empty());
+
+        let expanded = pdb.expand_ncs(&['A'], |_, _| 'B');
+        let atoms = expanded.coordinates().all().cloned().collect::<Vec<_>>();
+        assert_eq!(atoms.len(), 5);
+        assert_eq!((atoms[3].serial, atoms[3].chain_id), (4, 'B'));
+        assert_eq!((atoms[3].x, atoms[3].y), (-6.25, -42.032));
+        assert_eq!(atoms[4].residue_name, "NAG");
+        assert_eq!(expanded.coordinates().hetatms().count(), 3);
+
+        let with_waters = pdb.ncs_copies(&['A', 'W'], |_, chain| chain);
+        assert_eq!(with_waters.len(), 3);
+    }
+
+    #[test]
+    fn coordinates() {
+        if let Ok((_, res)) = crate::parse(
+            r#"HEADER    LIGASE                                  15-OCT-98   1BYI              
+ATOM      1  N   SER A   1      16.250  42.032  25.571  1.00 20.91           N  
+ATOM      2  CA  SER A   1      15.493  40.784  25.264  1.00 21.24           C  
+HETATM 1849  O   HOH A 301      -5.012  21.230  -1.005  1.00 12.02           O  
+"#,
+        ) {
+            let coordinates = res.coordinates();
+            assert_eq!(coordinates.atoms().count(), 2);
+            assert_eq!(coordinates.hetatms().next().unwrap().residue_name, "HOH");
+            assert_eq!(
+                coordinates.all().map(|a| a.serial).collect::<Vec<_>>(),
+                vec![1, 2, 1849]
+            );
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn models() {
+        if let Ok((_, res)) = crate::parse(
+            r#"NUMMDL    2                                                                     
+MODEL        1                                                                  
+ATOM      1  N   MET A   1      -9.003   4.156  -0.974  1.00  0.00           N  
+ATOM      2  CA  MET A   1      -8.023   3.083  -1.245  1.00  0.00           C  
+ENDMDL                                                                          
+MODEL        2                                                                  
+ATOM      1  N   MET A   1      -8.832   3.980  -1.196  1.00  0.00           N  
+ENDMDL                                                                          
+"#,
+        ) {
+            let models = res.models().collect::<Vec<_>>();
+            assert_eq!(models.len(), 2);
+            assert_eq!(models[0].serial, 1);
+            assert_eq!(models[0].coordinates().atoms().count(), 2);
+            assert_eq!(models[1].serial, 2);
+            assert_eq!(models[1].coordinates().atoms().next().unwrap().x, -8.832);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn remarks() {
+        let (_, res) = crate::parse(
+            r#"REMARK   2
+REMARK   2 RESOLUTION.    0.97 ANGSTROMS.
+REMARK   3
+REMARK   3 REFINEMENT.
+REMARK   3   PROGRAM     : SHELXL-97, X-PLOR
+REMARK 465 MISSING RESIDUES
+"#,
+        )
+        .unwrap();
+        let remarks = res.remarks();
+        assert_eq!(remarks.keys().copied().collect::<Vec<_>>(), vec![2, 3, 465]);
+        assert_eq!(remarks[&2][1], "RESOLUTION.    0.97 ANGSTROMS.");
+        assert_eq!(remarks[&3][2], "  PROGRAM     : SHELXL-97, X-PLOR");
+    }
+
+    #[test]
+    fn resolution_and_refinement() {
+        use super::*;
+        let contents = read_file(&get_test_file_path("1BXO.pdb"));
+        let (_, pdb) = crate::parse(&contents).unwrap();
+        let header = pdb.header();
+        assert_eq!(header.resolution(), Some(Resolution::Angstroms(0.95)));
+        let refinement = header.refinement().unwrap();
+        assert_eq!(refinement.program, Some("SHELXL-97".to_owned()));
+        assert_eq!(refinement.resolution_low, Some(10.0));
+        assert_eq!(refinement.number_of_reflections, Some(430728));
+        assert_eq!(refinement.r_work, Some(0.099));
+        assert_eq!(refinement.r_free, Some(0.125));
+    }
+
+    #[test]
+    fn biological_assembly() {
+        let (_, res) = crate::parse(
+            r#"REMARK 350 BIOMOLECULE: 1
+REMARK 350 APPLY THE FOLLOWING TO CHAINS: A
+REMARK 350   BIOMT1   1  1.000000  0.000000  0.000000        0.00000
+REMARK 350   BIOMT2   1  0.000000  1.000000  0.000000        0.00000
+REMARK 350   BIOMT3   1  0.000000  0.000000  1.000000        0.00000
+REMARK 350   BIOMT1   2 -1.000000  0.000000  0.000000       10.00000
+REMARK 350   BIOMT2   2  0.000000 -1.000000  0.000000        0.00000
+REMARK 350   BIOMT3   2  0.000000  0.000000  1.000000        0.00000
+ATOM      1  N   SER A   1      16.250  42.032  25.571  1.00 20.91           N
+ATOM      2  N   GLY B   2      10.250  42.032  25.571  1.00 20.91           N
+"#,
+        )
+        .unwrap();
+        assert_eq!(res.assemblies()[0].operators.len(), 2);
+        assert!(res.biological_assembly(2).is_none());
+
+        let assembly = res.biological_assembly(1).unwrap();
+        let models = assembly.models().collect::<Vec<_>>();
+        assert_eq!(models.len(), 2);
+        let atoms = models[1].coordinates().atoms().cloned().collect::<Vec<_>>();
+        assert_eq!(atoms.len(), 1);
+        assert_eq!((atoms[0].x, atoms[0].y), (-6.25, -42.032));
+    }
+
+    #[test]
+    fn sequences() {
+        let contents = read_file(&get_test_file_path("1BYI.pdb"));
+        let (_, pdb) = crate::parse(&contents).unwrap();
+        let sequences = pdb.primary().sequences().unwrap();
+        assert_eq!(sequences.keys().collect::<Vec<_>>(), vec![&'A']);
+        assert_eq!(sequences[&'A'].len(), 224);
+        assert_eq!(sequences[&'A'][0], "SER");
+
+        let (_, pdb) = crate::parse(
+            r#"SEQRES   1 A    3  SER LYS
+SEQRES   1 B    1  HOH
+"#,
+        )
+        .unwrap();
+        let error = pdb.primary().sequences().unwrap_err();
+        assert_eq!((error.chain_id, error.num_res, error.residues), ('A', 3, 2));
+
+        let (rest, pdb) = crate::parse(
+            r#"SEQRES   1 A   15  SER LYS ARG TYR PHE VAL THR GLY THR ASP THR GLU VAL
+SEQRES   2 A   16  GLY LYS
+ATOM      1  N   SER A   1      16.250  42.032  25.571  1.00 20.91           N
+"#,
+        )
+        .unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(pdb.records().len(), 2);
+        let error = pdb.primary().sequences().unwrap_err();
+        assert_eq!(error.conflicting_num_res, Some(16));
+        assert_eq!(
+            error.to_string(),
+            "chain A: numRes is 15 but a following SEQRES record gives 16"
+        );
+    }
+
+    #[test]
+    fn modified_residues() {
+        let (_, pdb) = crate::parse(
+            r#"SEQRES   1 A    4  MET MSE SEP GLY
+MODRES 1ABC MSE A    2  MET  SELENOMETHIONINE
+MODRES 1ABC SEP A    3A SER  PHOSPHOSERINE
+MODRES 1ABC SEP A   -3  SER  PHOSPHOSERINE
+"#,
+        )
+        .unwrap();
+        let primary = pdb.primary();
+        assert_eq!(primary.modified_residues().len(), 3);
+        assert_eq!(
+            primary
+                .modified_residue('A', 3, Some('A'))
+                .unwrap()
+                .residue_name,
+            "SEP"
+        );
+        assert!(primary.modified_residue('A', 3, None).is_none());
+        assert!(primary.modified_residue('A', -3, None).is_some());
+        assert_eq!(primary.standard_residue_name("MSE"), Some("MET"));
+        assert_eq!(primary.standard_residue_name("GLY"), None);
+        assert_eq!(
+            primary.standard_sequences().unwrap()[&'A'],
+            vec!["MET", "MET", "SER", "GLY"]
+        );
+    }
+
+    #[test]
+    fn heterogens() {
+        let contents = read_file(&get_test_file_path("1BXO.pdb"));
+        let (_, pdb) = crate::parse(&contents).unwrap();
+        let heterogens = pdb.heterogens();
+        assert_eq!(heterogens.hets().count(), 6);
+        assert_eq!(heterogens.instances("GOL").count(), 2);
+        assert_eq!(heterogens.name("SO4"), Some("SULFATE ION"));
+        assert_eq!(
+            heterogens.name("PP7"),
+            Some("METHYL CYCLO[(2S)-2-[[(1R)-1-(N-(L-N-(3- METHYLBUTANOYL)VALYL-L-ASPARTYL)AMINO)-3- \
+                  METHYLBUTYL]HYDROXYPHOSPHINYLOXY]-3-(3-AMINOMETHYL) PHENYLPROPANOATE")
+        );
+        assert_eq!(
+            heterogens.synonyms_of("GOL"),
+            vec!["GLYCERIN", "PROPANE-1,2,3-TRIOL"]
+        );
+        assert!(heterogens.synonyms_of("MAN").is_empty());
+        let water = heterogens.formula("HOH").unwrap();
+        assert_eq!((water.component_number, water.water), (8, true));
+        assert_eq!(heterogens.formula("SO4").unwrap().formula, "O4 S 2-");
+    }
+
+    #[test]
+    fn secondary_structure() {
+        use super::*;
+        let contents = read_file(&get_test_file_path("1BXO.pdb"));
+        let (_, pdb) = crate::parse(&contents).unwrap();
+        let secondary = pdb.secondary_structure();
+        assert!(secondary.helices().count() > 0);
+        assert_eq!(secondary.sheet("A").count(), 8);
+        match secondary.element_at('A', 13, None) {
+            Some(SecondaryStructureElement::Helix(h)) => assert_eq!(h.serial, 1),
+            _ => panic!(),
+        }
+        match secondary.element_at('A', 28, None) {
+            Some(SecondaryStructureElement::Strand(s)) => {
+                assert_eq!((s.sheet_id.as_str(), s.strand), ("A", 1))
+            }
+            _ => panic!(),
+        }
+        assert!(secondary.element_at('A', 1, None).is_none());
+    }
+
+    #[test]
+    fn connectivity() {
+        let contents = read_file(&get_test_file_path("1BXO.pdb"));
+        let (_, pdb) = crate::parse(&contents).unwrap();
+        let connectivity = pdb.connectivity();
+        let disulfide = connectivity.disulfides().next().unwrap();
+        assert_eq!(
+            (
+                disulfide.residue1.sequence_number,
+                disulfide.residue2.sequence_number
+            ),
+            (249, 283)
+        );
+        assert_eq!(connectivity.links().count(), 2);
+        let link = connectivity.links_of('A', 7, None).next().unwrap();
+        assert_eq!(
+            (link.atom1.residue_name.as_str(), link.atom2.name.as_str()),
+            ("MAN", "OG1")
+        );
+        let omegas = connectivity
+            .cis_peptides()
+            .map(|c| c.omega)
+            .collect::<Vec<_>>();
+        assert_eq!(omegas, vec![7.19, -4.15]);
+    }
+
+    #[test]
+    fn sites() {
+        let contents = read_file(&get_test_file_path("1BXO.pdb"));
+        let (_, pdb) = crate::parse(&contents).unwrap();
+        let sites = pdb.sites();
+        assert_eq!(sites.all().count(), 7);
+        assert_eq!(sites.residues("AC4").len(), 21);
+        assert_eq!(sites.residues("AC6")[5].sequence_number, 830);
+        assert!(sites.residues("XYZ").is_empty());
+        let described = sites.described();
+        let (site, description) = &described[0];
+        assert_eq!(site.site_id, "CIC");
+        assert_eq!(
+            description.as_ref().unwrap().description.as_deref(),
+            Some("CATALYTIC RESIDUES")
+        );
+        assert_eq!(
+            sites.descriptions()["AC2"].description.as_deref(),
+            Some("BINDING SITE FOR RESIDUE MAN A 329")
+        );
+    }
+
+    #[test]
+    fn crystal() {
+        use super::*;
+        let contents = read_file(&get_test_file_path("1BXO.pdb"));
+        let (_, pdb) = crate::parse(&contents).unwrap();
+        let crystal = pdb.crystal();
+        assert_eq!(crystal.cryst1().unwrap().space_group, "C 1 2 1");
+        assert_eq!(crystal.origx(), Some(Matrix3x4::identity()));
+        assert_eq!(crystal.ncs_operators().count(), 0);
+        let point = [37.571, 31.701, 0.575];
+        let fractional = crystal.to_fractional(point).unwrap();
+        assert!((fractional[1] - 31.701 * 0.021436).abs() < 1e-9);
+        let orthogonal = crystal.to_orthogonal(fractional).unwrap();
+        for (o, p) in orthogonal.iter().zip(point.iter()) {
+            assert!((o - p).abs() < 1e-9);
+        }
+    }
 }
@@ -0,0 +1,414 @@
+/*!
+Hierarchical Structure → Model → Chain → Residue → Atom view of a parsed pdb entry.
+
+A [Structure](struct.Structure.html) is built from a [PdbFile](../struct.PdbFile.html) and keeps
+its nodes in flat arenas, so every node can be navigated down to its children and up to its parent.
+Chains are keyed by chain identifier, residues by residue sequence number and insertion code and
+atoms by atom name and alternate location indicator. Children are iterated in file order.
+*/
+use super::{
+    pdb_file::PdbFile,
+    types::{Anisou, Atom as AtomRecord, Record},
+};
+use std::{collections::HashMap, ops::Deref};
+
+/// key of a residue inside its chain: residue sequence number and insertion code
+pub type ResidueKey = (i32, Option<char>);
+
+/// key of an atom inside its residue: atom name and alternate location indicator
+pub type AtomKey = (String, Option<char>);
+
+#[derive(Debug, Clone)]
+struct ModelNode {
+    serial: u32,
+    chains: Vec<usize>,
+    chain_index: HashMap<char, usize>,
+}
+
+#[derive(Debug, Clone)]
+struct ChainNode {
+    id: char,
+    model: usize,
+    residues: Vec<usize>,
+    residue_index: HashMap<ResidueKey, usize>,
+}
+
+#[derive(Debug, Clone)]
+struct ResidueNode {
+    name: String,
+    key: ResidueKey,
+    hetero: bool,
+    chain: usize,
+    atoms: Vec<usize>,
+    atom_index: HashMap<AtomKey, usize>,
+}
+
+#[derive(Debug, Clone)]
+struct AtomNode {
+    atom: AtomRecord,
+    anisou: Option<Anisou>,
+    residue: usize,
+}
+
+/// root of the hierarchy. Owns every model, chain, residue and atom of the entry
+#[derive(Debug, Clone, Default)]
+pub struct Structure {
+    models: Vec<ModelNode>,
+    chains: Vec<ChainNode>,
+    residues: Vec<ResidueNode>,
+    atoms: Vec<AtomNode>,
+}
+
+impl Structure {
+    /// models in file order
+    pub fn models(&self) -> impl Iterator<Item = Model<'_>> {
+        (0..self.models.len()).map(move |index| Model {
+            structure: self,
+            index,
+        })
+    }
+
+    /// model with the given serial number
+    pub fn model(&self, serial: u32) -> Option<Model<'_>> {
+        self.models().find(|m| m.serial() == serial)
+    }
+
+    /// every atom of every model in file order
+    pub fn atoms(&self) -> impl Iterator<Item = Atom<'_>> {
+        (0..self.atoms.len()).map(move |index| Atom {
+            structure: self,
+            index,
+        })
+    }
+
+    fn add_model(&mut self, serial: u32) -> usize {
+        self.models.push(ModelNode {
+            serial,
+            chains: Vec::new(),
+            chain_index: HashMap::new(),
+        });
+        self.models.len() - 1
+    }
+
+    fn add_atom(&mut self, model: usize, atom: &AtomRecord, anisou: Option<&Anisou>, hetero: bool) {
+        let chain = match self.models[model].chain_index.get(&atom.chain_id) {
+            Some(&chain) => chain,
+            None => {
+                let chain = self.chains.len();
+                self.chains.push(ChainNode {
+                    id: atom.chain_id,
+                    model,
+                    residues: Vec::new(),
+                    residue_index: HashMap::new(),
+                });
+                self.models[model].chains.push(chain);
+                self.models[model].chain_index.insert(atom.chain_id, chain);
+                chain
+            }
+        };
+
+        let key = (atom.sequence_number, atom.insertion_code);
+        let residue = match self.chains[chain].residue_index.get(&key) {
+            Some(&residue) => residue,
+            None => {
+                let residue = self.residues.len();
+                self.residues.push(ResidueNode {
+                    name: atom.residue_name.clone(),
+                    key,
+                    hetero,
+                    chain,
+                    atoms: Vec::new(),
+                    atom_index: HashMap::new(),
+                });
+                self.chains[chain].residues.push(residue);
+                self.chains[chain].residue_index.insert(key, residue);
+                residue
+            }
+        };
+
+        let index = self.atoms.len();
+        self.atoms.push(AtomNode {
+            atom: atom.clone(),
+            anisou: anisou.cloned(),
+            residue,
+        });
+        self.residues[residue].atoms.push(index);
+        self.residues[residue]
+            .atom_index
+            .insert((atom.name.clone(), atom.alt_loc), index);
+    }
+}
+
+impl From<&PdbFile<Vec<Record>>> for Structure {
+    fn from(pdb: &PdbFile<Vec<Record>>) -> Self {
+        let mut structure = Structure::default();
+        for coordinate_model in pdb.models() {
+            let model = structure.add_model(coordinate_model.serial);
+            let anisous = coordinate_model
+                .records
+                .iter()
+                .filter_map(|record| match record {
+                    Record::Anisou(anisou) => Some((anisou.serial, anisou)),
+                    _ => None,
+                })
+                .collect::<HashMap<_, _>>();
+            for record in coordinate_model.records {
+                match record {
+                    Record::Atom(atom) => {
+                        let anisou = anisous.get(&atom.serial).copied();
+                        structure.add_atom(model, atom, anisou, false)
+                    }
+                    Record::Hetatm(atom) => {
+                        let anisou = anisous.get(&atom.serial).copied();
+                        structure.add_atom(model, atom, anisou, true)
+                    }
+                    _ => {}
+                }
+            }
+        }
+        structure
+    }
+}
+
+impl From<PdbFile<Vec<Record>>> for Structure {
+    fn from(pdb: PdbFile<Vec<Record>>) -> Self {
+        Structure::from(&pdb)
+    }
+}
+
+/// a single model of the structure
+#[derive(Debug, Clone, Copy)]
+pub struct Model<'a> {
+    structure: &'a Structure,
+    index: usize,
+}
+
+impl<'a> Model<'a> {
+    fn node(&self) -> &'a ModelNode {
+        &self.structure.models[self.index]
+    }
+
+    pub fn serial(&self) -> u32 {
+        self.node().serial
+    }
+
+    /// chains of this model in file order
+    pub fn chains(&self) -> impl Iterator<Item = Chain<'a>> {
+        let structure = self.structure;
+        self.node()
+            .chains
+            .iter()
+            .map(move |&index| Chain { structure, index })
+    }
+
+    /// chain with the given chain identifier
+    pub fn chain(&self, id: char) -> Option<Chain<'a>> {
+        self.node().chain_index.get(&id).map(|&index| Chain {
+            structure: self.structure,
+            index,
+        })
+    }
+}
+
+/// a chain of a model
+#[derive(Debug, Clone, Copy)]
+pub struct Chain<'a> {
+    structure: &'a Structure,
+    index: usize,
+}
+
+impl<'a> Chain<'a> {
+    fn node(&self) -> &'a ChainNode {
+        &self.structure.chains[self.index]
+    }
+
+    pub fn id(&self) -> char {
+        self.node().id
+    }
+
+    /// model containing this chain
+    pub fn model(&self) -> Model<'a> {
+        Model {
+            structure: self.structure,
+            index: self.node().model,
+        }
+    }
+
+    /// residues of this chain in file order
+    pub fn residues(&self) -> impl Iterator<Item = Residue<'a>> {
+        let structure = self.structure;
+        self.node()
+            .residues
+            .iter()
+            .map(move |&index| Residue { structure, index })
+    }
+
+    /// residue with the given sequence number and insertion code
+    pub fn residue(
+        &self,
+        sequence_number: i32,
+        insertion_code: Option<char>,
+    ) -> Option<Residue<'a>> {
+        self.node()
+            .residue_index
+            .get(&(sequence_number, insertion_code))
+            .map(|&index| Residue {
+                structure: self.structure,
+                index,
+            })
+    }
+}
+
+/// a residue of a chain
+#[derive(Debug, Clone, Copy)]
+pub struct Residue<'a> {
+    structure: &'a Structure,
+    index: usize,
+}
+
+impl<'a> Residue<'a> {
+    fn node(&self) -> &'a ResidueNode {
+        &self.structure.residues[self.index]
+    }
+
+    pub fn name(&self) -> &'a str {
+        &self.node().name
+    }
+
+    pub fn sequence_number(&self) -> i32 {
+        self.node().key.0
+    }
+
+    pub fn insertion_code(&self) -> Option<char> {
+        self.node().key.1
+    }
+
+    /// true if the residue is built from HETATM records
+    pub fn is_hetero(&self) -> bool {
+        self.node().hetero
+    }
+
+    /// chain containing this residue
+    pub fn chain(&self) -> Chain<'a> {
+        Chain {
+            structure: self.structure,
+            index: self.node().chain,
+        }
+    }
+
+    /// atoms of this residue in file order
+    pub fn atoms(&self) -> impl Iterator<Item = Atom<'a>> {
+        let structure = self.structure;
+        self.node()
+            .atoms
+            .iter()
+            .map(move |&index| Atom { structure, index })
+    }
+
+    /// atom with the given name and alternate location indicator
+    pub fn atom(&self, name: &str, alt_loc: Option<char>) -> Option<Atom<'a>> {
+        self.node()
+            .atom_index
+            .get(&(name.to_owned(), alt_loc))
+            .map(|&index| Atom {
+                structure: self.structure,
+                index,
+            })
+    }
+}
+
+/// an atom of a residue. Dereferences to the parsed
+/// [Atom](../struct.Atom.html) record
+#[derive(Debug, Clone, Copy)]
+pub struct Atom<'a> {
+    structure: &'a Structure,
+    index: usize,
+}
+
+impl<'a> Atom<'a> {
+    /// residue containing this atom
+    pub fn residue(&self) -> Residue<'a> {
+        Residue {
+            structure: self.structure,
+            index: self.structure.atoms[self.index].residue,
+        }
+    }
+
+    /// anisotropic temperature factors given in the ANISOU record
+    /// with the serial number of this atom
+    pub fn anisou(&self) -> Option<&'a Anisou> {
+        self.structure.atoms[self.index].anisou.as_ref()
+    }
+}
+
+impl<'a> Deref for Atom<'a> {
+    type Target = AtomRecord;
+    fn deref(&self) -> &AtomRecord {
+        &self.structure.atoms[self.index].atom
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Structure;
+    use crate::record::test::{get_test_file_path, read_file};
+
+    #[test]
+    fn hierarchy() {
+        let (_, pdb) = crate::parse(
+            r#"ATOM      1  N   SER A   1      16.250  42.032  25.571  1.00 20.91           N
+ATOM      2  CA  SER A   1      15.493  40.784  25.264  1.00 21.24           C
+ANISOU    2  CA  SER A   1     1031   2118   1764   -123   -102    531       C
+ATOM      3  CB ASER A   1      15.133  39.898  26.453  0.50 23.00           C
+ATOM      4  CB BSER A   1      15.233  39.998  26.553  0.50 23.00           C
+ATOM      5  N   LYS A   2      14.250  41.032  24.571  1.00 20.91           N
+ATOM      6  N   GLY B   2A     10.250  42.032  25.571  1.00 20.91           N
+HETATM    7  O   HOH B 301      -5.012  21.230  -1.005  1.00 12.02           O
+"#,
+        )
+        .unwrap();
+        let structure = Structure::from(&pdb);
+        let model = structure.model(1).unwrap();
+        assert_eq!(
+            model.chains().map(|c| c.id()).collect::<Vec<_>>(),
+            vec!['A', 'B']
+        );
+
+        let chain_a = model.chain('A').unwrap();
+        assert_eq!(chain_a.residues().count(), 2);
+        let serine = chain_a.residue(1, None).unwrap();
+        assert_eq!(serine.name(), "SER");
+        assert_eq!(serine.atoms().count(), 4);
+        let cb = serine.atom("CB", Some('B')).unwrap();
+        assert_eq!(cb.serial, 4);
+        assert_eq!(cb.residue().chain().model().serial(), 1);
+        assert!(cb.anisou().is_none());
+        assert_eq!(serine.atom("CA", None).unwrap().anisou().unwrap().u22, 2118);
+
+        let chain_b = model.chain('B').unwrap();
+        assert_eq!(chain_b.residue(2, Some('A')).unwrap().name(), "GLY");
+        assert!(chain_b.residue(301, None).unwrap().is_hetero());
+        assert_eq!(structure.atoms().count(), 7);
+    }
+
+    #[test]
+    fn anisotropic_atoms() {
+        let contents = read_file(&get_test_file_path("1BXO.pdb"));
+        let (_, pdb) = crate::parse(&contents).unwrap();
+        let structure = Structure::from(&pdb);
+        let anisous = structure
+            .atoms()
+            .filter_map(|a| a.anisou().map(|anisou| (a.serial, anisou)))
+            .collect::<Vec<_>>();
+        assert_eq!(anisous.len(), pdb.coordinates().anisous().count());
+        for (serial, anisou) in anisous {
+            assert_eq!(anisou.serial, serial);
+            let anisotropy = anisou.anisotropy().unwrap();
+            assert!(anisotropy > 0.0 && anisotropy <= 1.0);
+        }
+        let nitrogen = structure.atoms().find(|a| a.serial == 1).unwrap();
+        let anisou = nitrogen.anisou().unwrap();
+        assert_eq!([anisou.u11, anisou.u22, anisou.u33], [1031, 2118, 1764]);
+        assert!((anisou.equivalent_b() - nitrogen.temp_factor).abs() < 0.05);
+    }
+}
@@ -1,6 +1,7 @@
 use chrono::NaiveDate;
-use std::{marker::PhantomData, str::FromStr};
+use std::{fmt, marker::PhantomData, ops::Range, str::FromStr};
 
+#[allow(dead_code)]
 #[derive(Debug)]
 pub(crate) struct Continuation<T> {
     pub continuation: u32,
@@ -44,6 +45,177 @@ impl FromStr for ExperimentalTechnique {
     }
 }
 
+impl fmt::Display for ExperimentalTechnique {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ExperimentalTechnique::XRayDiffraction => "X-RAY DIFFRACTION",
+            ExperimentalTechnique::FiberDiffraction => "FIBER DIFFRACTION",
+            ExperimentalTechnique::NeutronDiffraction => "NEUTRON DIFFRACTION",
+            ExperimentalTechnique::ElectronCrystallography => "ELECTRON CRYSTALLOGRAPHY",
+            ExperimentalTechnique::ElectronMicroscopy => "ELECTRON MICROSCOPY",
+            ExperimentalTechnique::SolidStateNmr => "SOLID-STATE NMR",
+            ExperimentalTechnique::SolutionNmr => "SOLUTION NMR",
+            ExperimentalTechnique::SolutionScattering => "SOLUTION SCATTERING",
+        })
+    }
+}
+
+/// location of a parsed record in the input text. Kept for every record
+/// when parsing in lossless mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceSpan {
+    /// byte range of the record in the input
+    pub range: Range<usize>,
+    /// original text of the record including line endings
+    pub text: String,
+}
+
+impl SourceSpan {
+    /// original lines of the record without line endings
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+}
+
+/// free text lines of REMARK records sharing the same remark number
+#[derive(Debug, Clone, Default)]
+pub struct Remark {
+    pub number: u32,
+    pub lines: Vec<String>,
+}
+
+/// resolution of the experiment given in REMARK 2
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resolution {
+    /// highest resolution in Angstroms
+    Angstroms(f64),
+    /// resolution is not applicable to the experimental technique
+    NotApplicable,
+}
+
+/// key refinement statistics given in REMARK 3
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Refinement {
+    pub program: Option<String>,
+    pub resolution_high: Option<f64>,
+    pub resolution_low: Option<f64>,
+    pub number_of_reflections: Option<u32>,
+    pub r_work: Option<f64>,
+    pub r_free: Option<f64>,
+    pub wilson_b_value: Option<f64>,
+    pub mean_b_value: Option<f64>,
+}
+
+/// 3x4 matrix of a rotation followed by a translation.
+/// Last column of every row holds the translation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix3x4 {
+    pub rows: [[f64; 4]; 3],
+}
+
+impl Default for Matrix3x4 {
+    fn default() -> Self {
+        Matrix3x4::identity()
+    }
+}
+
+impl Matrix3x4 {
+    pub fn identity() -> Self {
+        Matrix3x4 {
+            rows: [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+            ],
+        }
+    }
+
+    /// transforms a point
+    pub fn apply(&self, point: [f64; 3]) -> [f64; 3] {
+        let row = |r: &[f64; 4]| r[0] * point[0] + r[1] * point[1] + r[2] * point[2] + r[3];
+        [row(&self.rows[0]), row(&self.rows[1]), row(&self.rows[2])]
+    }
+
+    /// transformation applying `other` first and `self` afterwards
+    pub fn multiply(&self, other: &Matrix3x4) -> Matrix3x4 {
+        let mut product = Matrix3x4::identity();
+        for r in 0..3 {
+            for c in 0..4 {
+                product.rows[r][c] = (0..3)
+                    .map(|k| self.rows[r][k] * other.rows[k][c])
+                    .sum::<f64>();
+            }
+            product.rows[r][3] += self.rows[r][3];
+        }
+        product
+    }
+
+    /// inverse transformation. Returns `None` if the rotation part is singular
+    pub fn inverse(&self) -> Option<Matrix3x4> {
+        let m = |r: usize, c: usize| self.rows[r][c];
+        let cofactor = |r: usize, c: usize| {
+            let (r1, r2) = ((r + 1) % 3, (r + 2) % 3);
+            let (c1, c2) = ((c + 1) % 3, (c + 2) % 3);
+            m(r1, c1) * m(r2, c2) - m(r1, c2) * m(r2, c1)
+        };
+        let determinant = (0..3).map(|c| m(0, c) * cofactor(0, c)).sum::<f64>();
+        if determinant.abs() < f64::EPSILON {
+            return None;
+        }
+        let mut inverse = Matrix3x4::identity();
+        for r in 0..3 {
+            for c in 0..3 {
+                inverse.rows[r][c] = cofactor(c, r) / determinant;
+            }
+        }
+        let translation = inverse.apply([-m(0, 3), -m(1, 3), -m(2, 3)]);
+        for (r, t) in translation.iter().enumerate() {
+            inverse.rows[r][3] = *t;
+        }
+        Some(inverse)
+    }
+}
+
+/// BIOMT operator of REMARK 350 and the chains it is applied to
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AssemblyOperator {
+    pub serial: u32,
+    pub chains: Vec<char>,
+    pub matrix: Matrix3x4,
+}
+
+/// biological assembly given in REMARK 350
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BiologicalAssembly {
+    pub number: u32,
+    pub operators: Vec<AssemblyOperator>,
+}
+
+/// location of a residue given as model serial number, chain identifier,
+/// residue sequence number and insertion code
+pub type ResidueLocation = (u32, char, i32, Option<char>);
+
+/// residue without coordinates listed in REMARK 465
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MissingResidue {
+    pub model: u32,
+    pub residue_name: String,
+    pub chain_id: char,
+    pub sequence_number: i32,
+    pub insertion_code: Option<char>,
+}
+
+/// residue having atoms without coordinates listed in REMARK 470
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MissingAtoms {
+    pub model: u32,
+    pub residue_name: String,
+    pub chain_id: char,
+    pub sequence_number: i32,
+    pub insertion_code: Option<char>,
+    pub atoms: Vec<String>,
+}
+
 /// Represents keys of CMPND and SOURCE records
 #[derive(Debug, PartialEq, Clone)]
 pub enum Token {
@@ -132,7 +304,7 @@ impl std::default::Default for Header {
     fn default() -> Self {
         Header {
             classification: String::default(),
-            deposition_date: NaiveDate::from_ymd(1900, 1, 1),
+            deposition_date: NaiveDate::from_ymd_opt(1900, 1, 1).unwrap(),
             id_code: String::default(),
         }
     }
@@ -155,7 +327,7 @@ pub struct Obslte {
 impl std::default::Default for Obslte {
     fn default() -> Self {
         Obslte {
-            replacement_date: NaiveDate::from_ymd(1900, 1, 1),
+            replacement_date: NaiveDate::from_ymd_opt(1900, 1, 1).unwrap(),
             replacement_ids: Vec::new(),
         }
     }
@@ -187,7 +359,7 @@ pub struct Sprsde {
 impl std::default::Default for Sprsde {
     fn default() -> Self {
         Sprsde {
-            sprsde_date: NaiveDate::from_ymd(1900, 1, 1),
+            sprsde_date: NaiveDate::from_ymd_opt(1900, 1, 1).unwrap(),
             superseeded: Vec::new(),
             id_code: String::default(),
         }
@@ -197,7 +369,13 @@ impl std::default::Default for Sprsde {
 #[derive(Debug, Clone, Default)]
 pub struct Seqres {
     pub chain_id: Option<char>,
+    /// number of residues in the chain as given on every SEQRES line
+    pub num_res: u32,
     pub residues: Vec<String>,
+    /// first numRes value of a following line of the chain differing from `num_res`
+    pub conflicting_num_res: Option<u32>,
+    /// number of lines the record was read from, zero for records built in code
+    pub source_lines: u32,
 }
 
 /// model type of the entry
@@ -345,7 +523,7 @@ pub struct Seqadv {
     pub idcode: String,
     pub conflicting_residue: String,
     pub chain_id: char,
-    pub sequence_number: u32,
+    pub sequence_number: i32,
     pub insertion_code: Option<char>,
     pub database: String,
     pub db_accession: String,
@@ -360,12 +538,481 @@ pub struct Modres {
     pub idcode: String,
     pub residue_name: String,
     pub chain_id: char,
-    pub sequence_number: u32,
+    pub sequence_number: i32,
     pub insertion_code: Option<char>,
     pub standart_residue_name: String,
     pub comment: String,
 }
 
+/// atomic coordinate record. Used for both standard residue
+/// (ATOM) and heterogen (HETATM) atoms
+#[derive(Debug, Clone, Default)]
+pub struct Atom {
+    pub serial: u32,
+    pub name: String,
+    pub alt_loc: Option<char>,
+    pub residue_name: String,
+    pub chain_id: char,
+    pub sequence_number: i32,
+    pub insertion_code: Option<char>,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub occupancy: f64,
+    pub temp_factor: f64,
+    pub element: Option<String>,
+    pub charge: Option<i8>,
+}
+
+/// anisotropic temperature factors of an atom. Atom identification fields repeat those
+/// of the ATOM or HETATM record with the same serial number. U(ij) values are given in
+/// units of 10<sup>-4</sup> square angstroms as in the file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Anisou {
+    pub serial: u32,
+    pub name: String,
+    pub alt_loc: Option<char>,
+    pub residue_name: String,
+    pub chain_id: char,
+    pub sequence_number: i32,
+    pub insertion_code: Option<char>,
+    pub u11: i32,
+    pub u22: i32,
+    pub u33: i32,
+    pub u12: i32,
+    pub u13: i32,
+    pub u23: i32,
+    pub element: Option<String>,
+    pub charge: Option<i8>,
+}
+
+impl Anisou {
+    /// symmetric U tensor in square angstroms
+    pub fn tensor(&self) -> [[f64; 3]; 3] {
+        let u = |v: i32| f64::from(v) / 10000.0;
+        [
+            [u(self.u11), u(self.u12), u(self.u13)],
+            [u(self.u12), u(self.u22), u(self.u23)],
+            [u(self.u13), u(self.u23), u(self.u33)],
+        ]
+    }
+
+    /// equivalent isotropic temperature factor, 8π²/3 times the trace of the U tensor
+    pub fn equivalent_b(&self) -> f64 {
+        let tensor = self.tensor();
+        8.0 * std::f64::consts::PI.powi(2) * (tensor[0][0] + tensor[1][1] + tensor[2][2]) / 3.0
+    }
+
+    /// ratio of the smallest to the largest principal mean square displacement.
+    /// 1 for isotropic motion, approaching 0 as motion gets more anisotropic.
+    /// `None` if the tensor is not positive definite, as for an all zero tensor,
+    /// since such a tensor does not describe a displacement ellipsoid
+    pub fn anisotropy(&self) -> Option<f64> {
+        let axes = self.principal_axes();
+        if axes[2].0 > 0.0 {
+            Some(axes[2].0 / axes[0].0)
+        } else {
+            None
+        }
+    }
+
+    /// eigenvalues of the U tensor, which are the mean square displacements along
+    /// the principal axes, and the unit eigenvectors giving the axis directions.
+    /// Axes are sorted from the largest to the smallest displacement
+    pub fn principal_axes(&self) -> [(f64, [f64; 3]); 3] {
+        let mut a = self.tensor();
+        let mut v = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        // cyclic Jacobi rotations zeroing the off diagonal elements
+        for _ in 0..50 {
+            let off_diagonal = a[0][1].abs() + a[0][2].abs() + a[1][2].abs();
+            if off_diagonal < 1e-15 {
+                break;
+            }
+            for &(p, q) in &[(0, 1), (0, 2), (1, 2)] {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (row_p, row_q) = (a[p], a[q]);
+                for k in 0..3 {
+                    a[p][k] = c * row_p[k] - s * row_q[k];
+                    a[q][k] = s * row_p[k] + c * row_q[k];
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+        let axis = |i: usize| (a[i][i], [v[0][i], v[1][i], v[2][i]]);
+        let mut axes = [axis(0), axis(1), axis(2)];
+        axes.sort_by(|x, y| y.0.partial_cmp(&x.0).unwrap_or(std::cmp::Ordering::Equal));
+        axes
+    }
+}
+
+/// end of a chain in the coordinate section. Serial number and residue
+/// fields are frequently omitted
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ter {
+    pub serial: Option<u32>,
+    pub residue: Option<ResidueReference>,
+}
+
+/// bonds of an atom given in CONECT records. Consecutive records of the
+/// same atom are merged
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Conect {
+    pub serial: u32,
+    /// serial numbers of the bonded atoms
+    pub bonded: Vec<u32>,
+    /// number of lines the record was read from, zero for records built in code
+    pub source_lines: u32,
+}
+
+/// record counts of the entry given in the MASTER record
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Master {
+    pub num_remark: u32,
+    pub num_het: u32,
+    pub num_helix: u32,
+    pub num_sheet: u32,
+    pub num_turn: u32,
+    pub num_site: u32,
+    pub num_xform: u32,
+    pub num_coord: u32,
+    pub num_ter: u32,
+    pub num_conect: u32,
+    pub num_seq: u32,
+}
+
+/// starts a model of a multi model entry. Coordinate
+/// records up to the next ENDMDL record belong to this model
+#[derive(Debug, Clone, Default)]
+pub struct Model {
+    pub serial: u32,
+}
+
+/// a single instance of a non-standard group
+/// (ligand, cofactor, ion or water) present in the entry
+#[derive(Debug, Clone, Default)]
+pub struct Het {
+    pub het_id: String,
+    pub chain_id: char,
+    pub sequence_number: i32,
+    pub insertion_code: Option<char>,
+    pub num_het_atoms: u32,
+    pub text: String,
+}
+
+/// chemical name of a non-standard group
+#[derive(Debug, Clone, Default)]
+pub struct Hetnam {
+    pub het_id: String,
+    pub name: String,
+}
+
+/// synonyms of a non-standard group
+#[derive(Debug, Clone, Default)]
+pub struct Hetsyn {
+    pub het_id: String,
+    pub synonyms: Vec<String>,
+}
+
+/// chemical formula of a non-standard group
+#[derive(Debug, Clone, Default)]
+pub struct Formul {
+    pub component_number: u32,
+    pub het_id: String,
+    pub water: bool,
+    pub formula: String,
+}
+
+/// residue referenced by an annotation record
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResidueReference {
+    pub residue_name: String,
+    pub chain_id: char,
+    pub sequence_number: i32,
+    pub insertion_code: Option<char>,
+}
+
+impl ResidueReference {
+    /// whether the residue is at the given position
+    pub fn is_at(
+        &self,
+        chain_id: char,
+        sequence_number: i32,
+        insertion_code: Option<char>,
+    ) -> bool {
+        self.chain_id == chain_id
+            && self.sequence_number == sequence_number
+            && self.insertion_code == insertion_code
+    }
+}
+
+/// atom referenced by an annotation record
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AtomReference {
+    pub name: String,
+    pub alt_loc: Option<char>,
+    pub residue_name: String,
+    pub chain_id: char,
+    pub sequence_number: i32,
+    pub insertion_code: Option<char>,
+}
+
+/// helix classes as defined in HELIX records
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum HelixClass {
+    #[default]
+    RightHandedAlpha,
+    RightHandedOmega,
+    RightHandedPi,
+    RightHandedGamma,
+    RightHanded310,
+    LeftHandedAlpha,
+    LeftHandedOmega,
+    LeftHandedGamma,
+    Ribbon27,
+    Polyproline,
+}
+
+impl HelixClass {
+    /// class number used in HELIX records
+    pub fn number(self) -> u32 {
+        self as u32 + 1
+    }
+}
+
+impl FromStr for HelixClass {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1" => Ok(HelixClass::RightHandedAlpha),
+            "2" => Ok(HelixClass::RightHandedOmega),
+            "3" => Ok(HelixClass::RightHandedPi),
+            "4" => Ok(HelixClass::RightHandedGamma),
+            "5" => Ok(HelixClass::RightHanded310),
+            "6" => Ok(HelixClass::LeftHandedAlpha),
+            "7" => Ok(HelixClass::LeftHandedOmega),
+            "8" => Ok(HelixClass::LeftHandedGamma),
+            "9" => Ok(HelixClass::Ribbon27),
+            "10" => Ok(HelixClass::Polyproline),
+            _ => Err(format!("Unknown helix class {}", s)),
+        }
+    }
+}
+
+/// helix of the secondary structure
+#[derive(Debug, Clone, Default)]
+pub struct Helix {
+    pub serial: u32,
+    pub helix_id: String,
+    pub start: ResidueReference,
+    pub end: ResidueReference,
+    pub class: HelixClass,
+    pub comment: String,
+    pub length: u32,
+}
+
+/// sense of a strand with respect to the previous strand of its sheet
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum StrandSense {
+    #[default]
+    First,
+    Parallel,
+    AntiParallel,
+}
+
+/// hydrogen bonded atom pair registering a strand to the previous strand
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Registration {
+    pub current: AtomReference,
+    pub previous: AtomReference,
+}
+
+/// strand of a sheet. Every strand of a sheet is given in a separate SHEET record
+#[derive(Debug, Clone, Default)]
+pub struct Sheet {
+    pub strand: u32,
+    pub sheet_id: String,
+    pub num_strands: u32,
+    pub start: ResidueReference,
+    pub end: ResidueReference,
+    pub sense: StrandSense,
+    pub registration: Option<Registration>,
+}
+
+/// crystallographic symmetry operator given in `NNNMMM` format, where `NNN` is the
+/// operator number of the space group and `MMM` the unit cell translation offset by 5
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SymmetryOperator {
+    pub operator: u32,
+    pub translation: [i32; 3],
+}
+
+impl FromStr for SymmetryOperator {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let error = || format!("Invalid symmetry operator {}", s);
+        if s.len() < 4 || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(error());
+        }
+        let (operator, translation) = s.split_at(s.len() - 3);
+        let offset = |i: usize| i32::from(translation.as_bytes()[i] - b'0') - 5;
+        Ok(SymmetryOperator {
+            operator: operator.parse().map_err(|_| error())?,
+            translation: [offset(0), offset(1), offset(2)],
+        })
+    }
+}
+
+impl fmt::Display for SymmetryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.operator)?;
+        for t in self.translation.iter() {
+            write!(f, "{}", t + 5)?;
+        }
+        Ok(())
+    }
+}
+
+/// disulfide bond between two cysteine residues
+#[derive(Debug, Clone, Default)]
+pub struct Ssbond {
+    pub serial: u32,
+    pub residue1: ResidueReference,
+    pub residue2: ResidueReference,
+    pub symmetry1: Option<SymmetryOperator>,
+    pub symmetry2: Option<SymmetryOperator>,
+    pub length: Option<f64>,
+}
+
+/// connectivity between two atoms not implied by the residue sequence
+/// and not a disulfide bond
+#[derive(Debug, Clone, Default)]
+pub struct Link {
+    pub atom1: AtomReference,
+    pub atom2: AtomReference,
+    pub symmetry1: Option<SymmetryOperator>,
+    pub symmetry2: Option<SymmetryOperator>,
+    pub length: Option<f64>,
+}
+
+/// cis peptide bond between two residues
+#[derive(Debug, Clone, Default)]
+pub struct Cispep {
+    pub serial: u32,
+    pub residue1: ResidueReference,
+    pub residue2: ResidueReference,
+    pub model: u32,
+    pub omega: f64,
+}
+
+/// group of residues comprising a site such as a binding or catalytic site
+#[derive(Debug, Clone, Default)]
+pub struct Site {
+    pub site_id: String,
+    pub num_res: u32,
+    pub residues: Vec<ResidueReference>,
+    /// number of lines the record was read from, zero for records built in code
+    pub source_lines: u32,
+}
+
+/// description of a site given in REMARK 800
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SiteDescription {
+    pub site_id: String,
+    pub evidence_code: Option<String>,
+    pub description: Option<String>,
+}
+
+/// unit cell parameters, space group and Z value of a crystal structure
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Cryst1 {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub alpha: f64,
+    pub beta: f64,
+    pub gamma: f64,
+    pub space_group: String,
+    pub z: u32,
+}
+
+impl Cryst1 {
+    /// volume of the unit cell in cubic angstroms
+    pub fn volume(&self) -> f64 {
+        let (cos_alpha, cos_beta, cos_gamma) = (
+            self.alpha.to_radians().cos(),
+            self.beta.to_radians().cos(),
+            self.gamma.to_radians().cos(),
+        );
+        self.a
+            * self.b
+            * self.c
+            * (1.0 - cos_alpha.powi(2) - cos_beta.powi(2) - cos_gamma.powi(2)
+                + 2.0 * cos_alpha * cos_beta * cos_gamma)
+                .sqrt()
+    }
+
+    /// transformation from fractional to orthogonal coordinates in the standard PDB
+    /// orientation, where a is along x and b lies in the xy plane
+    pub fn orthogonalization_matrix(&self) -> Matrix3x4 {
+        let (cos_alpha, cos_beta) = (self.alpha.to_radians().cos(), self.beta.to_radians().cos());
+        let (sin_gamma, cos_gamma) = self.gamma.to_radians().sin_cos();
+        Matrix3x4 {
+            rows: [
+                [self.a, self.b * cos_gamma, self.c * cos_beta, 0.0],
+                [
+                    0.0,
+                    self.b * sin_gamma,
+                    self.c * (cos_alpha - cos_beta * cos_gamma) / sin_gamma,
+                    0.0,
+                ],
+                [0.0, 0.0, self.volume() / (self.a * self.b * sin_gamma), 0.0],
+            ],
+        }
+    }
+
+    /// transformation from orthogonal to fractional coordinates in the standard PDB orientation
+    pub fn fractionalization_matrix(&self) -> Matrix3x4 {
+        self.orthogonalization_matrix()
+            .inverse()
+            .unwrap_or_default()
+    }
+}
+
+/// non-crystallographic symmetry operator given in MTRIXn records
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mtrix {
+    pub serial: u32,
+    pub matrix: Matrix3x4,
+    /// whether coordinates of the copy generated by this operator are contained in the entry
+    pub given: bool,
+}
+
+/// crystallographic symmetry copy of the asymmetric unit. `operator` indexes the
+/// operators of the space group and `translation` is the unit cell shift applied
+/// after it. `matrix` is the combined transformation in orthogonal coordinates
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SymmetryMate {
+    pub operator: usize,
+    pub translation: [i32; 3],
+    pub matrix: Matrix3x4,
+}
+
 /// main enum unifying all record parser results.
 /// all sub parsers return a variant of this
 #[derive(Debug, Clone)]
@@ -398,5 +1045,33 @@ pub enum Record {
     Dbref2(Dbref2),
     Seqadv(Seqadv),
     Modres(Modres),
-    Remark,
+    Het(Het),
+    Hetnam(Hetnam),
+    Hetsyn(Hetsyn),
+    Formul(Formul),
+    Helix(Helix),
+    Sheet(Sheet),
+    Ssbond(Ssbond),
+    Link(Link),
+    Cispep(Cispep),
+    Site(Site),
+    Cryst1(Cryst1),
+    Origx(Matrix3x4),
+    Scale(Matrix3x4),
+    Mtrix(Mtrix),
+    Atom(Atom),
+    Hetatm(Atom),
+    Anisou(Anisou),
+    Ter(Ter),
+    Model(Model),
+    Endmdl,
+    Conect(Conect),
+    Master(Master),
+    End,
+    Remark(Remark),
+    /// line which could not be parsed in lenient mode
+    Unknown {
+        line_number: usize,
+        raw: String,
+    },
 }
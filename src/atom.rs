@@ -0,0 +1,209 @@
+/*!
+Contains parsers related to [Atom](http://www.wwpdb.org/documentation/file-format-content/format33/sect9.html#ATOM)
+and [Hetatm](http://www.wwpdb.org/documentation/file-format-content/format33/sect9.html#HETATM) records.
+The ATOM records present the atomic coordinates for standard amino acids and nucleotides. HETATM records
+present the atomic coordinates for atoms within non-standard groups. Both records share the same layout.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{
+    character::complete::{anychar, line_ending},
+    do_parse, map, named, take, take_str,
+};
+
+use std::str;
+
+/// parses formal charge of an atom given as `2+` or `1-`
+fn charge_parser(s: &str) -> Option<i8> {
+    let (magnitude, sign) = if s.ends_with('+') || s.ends_with('-') {
+        s.split_at(s.len() - 1)
+    } else {
+        (s.get(..1)?, s.get(1..)?)
+    };
+    let magnitude = magnitude.parse::<i8>().ok()?;
+    match sign {
+        "+" => Some(magnitude),
+        "-" => Some(-magnitude),
+        _ => None,
+    }
+}
+
+/// columns 77 - 80 are frequently omitted, so element and charge are
+/// read from the remaining part of the line if they exist. `element_column`
+/// is the index of column 77 in `rest`
+pub(crate) fn element_and_charge(
+    rest: &[u8],
+    element_column: usize,
+) -> (Option<String>, Option<i8>) {
+    (
+        optional_column(rest, element_column, element_column + 2).map(str::to_owned),
+        optional_column(rest, element_column + 2, element_column + 4).and_then(charge_parser),
+    )
+}
+
+named!(
+    coordinate_parser<Atom>,
+    do_parse!(
+        serial: fivedigit_hybrid36
+            >> take!(1)
+            >> name: map!(take_str!(4), str::trim)
+            >> alt_loc: optional_char
+            >> residue_name: map!(take_str!(3), str::trim)
+            >> take!(1)
+            >> chain_id: anychar
+            >> sequence_number: fourdigit_hybrid36
+            >> insertion_code: optional_char
+            >> take!(3)
+            >> x: eightdigit_real
+            >> y: eightdigit_real
+            >> z: eightdigit_real
+            >> occupancy: sixdigit_real
+            >> temp_factor: sixdigit_real
+            >> rest: till_line_ending
+            >> line_ending
+            >> ({
+                let (element, charge) = element_and_charge(rest, 10);
+                Atom {
+                    serial,
+                    name: name.to_owned(),
+                    alt_loc,
+                    residue_name: residue_name.to_owned(),
+                    chain_id,
+                    sequence_number,
+                    insertion_code,
+                    x,
+                    y,
+                    z,
+                    occupancy,
+                    temp_factor,
+                    element,
+                    charge,
+                }
+            })
+    )
+);
+
+named!(
+    #[doc=r#"Parses a single ATOM record. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing [Atom](../ast/types/struct.Atom.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD      | DEFINITION                                |
+|----------|--------------|------------|-------------------------------------------|
+| 1 -  6   | Record name  | "ATOM  "   |                                           |
+| 7 - 11   | Integer      | serial     | Atom  serial number.                      |
+| 13 - 16  | Atom         | name       | Atom name.                                |
+| 17       | Character    | altLoc     | Alternate location indicator.             |
+| 18 - 20  | Residue name | resName    | Residue name.                             |
+| 22       | Character    | chainID    | Chain identifier.                         |
+| 23 - 26  | Integer      | resSeq     | Residue sequence number.                  |
+| 27       | AChar        | iCode      | Code for insertion of residues.           |
+| 31 - 38  | Real(8.3)    | x          | Orthogonal coordinates for X in Angstroms.|
+| 39 - 46  | Real(8.3)    | y          | Orthogonal coordinates for Y in Angstroms.|
+| 47 - 54  | Real(8.3)    | z          | Orthogonal coordinates for Z in Angstroms.|
+| 55 - 60  | Real(6.2)    | occupancy  | Occupancy.                                |
+| 61 - 66  | Real(6.2)    | tempFactor | Temperature  factor.                      |
+| 77 - 78  | LString(2)   | element    | Element symbol, right-justified.          |
+| 79 - 80  | LString(2)   | charge     | Charge  on the atom.                      |
+"#],
+    pub atom_record_parser<Record>,
+    do_parse!(atom >> take!(2) >> res: coordinate_parser >> (Record::Atom(res)))
+);
+
+named!(
+    #[doc=r#"Parses a single HETATM record. Layout of the record is the same with ATOM record.
+If successfull returns [Record](../ast/types/enum.Record.html) variant containing [Atom](../ast/types/struct.Atom.html) instance.
+"#],
+    pub hetatm_record_parser<Record>,
+    do_parse!(hetatm >> res: coordinate_parser >> (Record::Hetatm(res)))
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn atom() {
+        if let Ok((_, Record::Atom(res))) = atom_record_parser(
+            "ATOM      2  CA  SER A   1      15.493  40.784  25.264  1.00 21.24           C  \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.serial, 2);
+            assert_eq!(res.name, "CA");
+            assert_eq!(res.alt_loc, None);
+            assert_eq!(res.residue_name, "SER");
+            assert_eq!(res.chain_id, 'A');
+            assert_eq!(res.sequence_number, 1);
+            assert_eq!(res.insertion_code, None);
+            assert_eq!(res.x, 15.493);
+            assert_eq!(res.y, 40.784);
+            assert_eq!(res.z, 25.264);
+            assert_eq!(res.occupancy, 1.0);
+            assert_eq!(res.temp_factor, 21.24);
+            assert_eq!(res.element, Some("C".to_owned()));
+            assert_eq!(res.charge, None);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn hetatm() {
+        if let Ok((_, Record::Hetatm(res))) = hetatm_record_parser(
+            "HETATM 1849 ZN    ZN A 301B     -5.012  21.230  -1.005  0.50 12.02          ZN2+\n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.serial, 1849);
+            assert_eq!(res.name, "ZN");
+            assert_eq!(res.residue_name, "ZN");
+            assert_eq!(res.sequence_number, 301);
+            assert_eq!(res.insertion_code, Some('B'));
+            assert_eq!(res.x, -5.012);
+            assert_eq!(res.occupancy, 0.5);
+            assert_eq!(res.element, Some("ZN".to_owned()));
+            assert_eq!(res.charge, Some(2));
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn hybrid36_atom() {
+        if let Ok((_, Record::Atom(res))) = atom_record_parser(
+            "ATOM  A0001  CA  GLY BA00A      15.493  40.784  25.264  1.00 21.24           C  \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.serial, 100_001);
+            assert_eq!(res.sequence_number, 10_010);
+            assert_eq!(res.insertion_code, None);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn atom_without_element() {
+        if let Ok((_, Record::Atom(res))) = atom_record_parser(
+            "ATOM    145  N  AVAL A  25A     32.433  16.336  57.540  0.50 11.92\n".as_bytes(),
+        ) {
+            assert_eq!(res.alt_loc, Some('A'));
+            assert_eq!(res.insertion_code, Some('A'));
+            assert_eq!(res.element, None);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn non_ascii_charge() {
+        if let Ok((_, Record::Hetatm(res))) = hetatm_record_parser(
+            "HETATM 1849 ZN    ZN A 301      -5.012  21.230  -1.005  0.50 12.02          ZN\u{e9}\n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.element, Some("ZN".to_owned()));
+            assert_eq!(res.charge, None);
+        } else {
+            panic!()
+        }
+    }
+}
@@ -0,0 +1,989 @@
+/*!
+Contains a reader for [PDBx/mmCIF](http://mmcif.wwpdb.org) files. Input is split into
+tokens of the CIF syntax, items of the first data block are collected into category
+tables and the core categories are mapped onto the [Record](../ast/types/enum.Record.html)
+types of the PDB format. Resulting [PdbFile](../ast/pdb_file/struct.PdbFile.html) offers
+the same views as a parsed PDB file.
+
+| CATEGORY                                     | RECORD                       |
+|----------------------------------------------|------------------------------|
+| entry, struct_keywords, pdbx_database_status | HEADER                       |
+| struct                                       | TITLE                        |
+| struct_keywords                              | KEYWDS                       |
+| exptl                                        | EXPDTA                       |
+| audit_author                                 | AUTHOR                       |
+| citation, citation_author                    | JRNL                         |
+| struct_ref, struct_ref_seq                   | DBREF                        |
+| pdbx_struct_mod_residue                      | MODRES                       |
+| atom_site                                    | MODEL, ATOM, HETATM, ENDMDL  |
+*/
+use super::{
+    ast::{pdb_file::*, types::*},
+    error::PdbParseError,
+};
+use chrono::NaiveDate;
+use std::{collections::HashMap, str::FromStr};
+
+/// a single token of the CIF syntax
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CifToken<'a> {
+    /// `data_` block header carrying the block name
+    DataBlock(&'a str),
+    /// `loop_` keyword
+    Loop,
+    /// data name such as `_atom_site.Cartn_x`
+    Tag(&'a str),
+    /// data value. Unquoted `.` (inapplicable) and `?` (unknown) are read as `None`
+    Value(Option<&'a str>),
+}
+
+/// items of a single category. Items given outside of a loop form a table with a single row
+#[derive(Debug, Clone, Default)]
+pub struct CifCategory<'a> {
+    name: String,
+    offset: usize,
+    items: Vec<String>,
+    values: Vec<(usize, Option<&'a str>)>,
+}
+
+/// categories of a data block keyed by lowercase category name
+#[derive(Debug, Clone, Default)]
+pub struct CifBlock<'a> {
+    pub name: &'a str,
+    categories: HashMap<String, CifCategory<'a>>,
+}
+
+impl<'a> CifBlock<'a> {
+    /// category table by name, case insensitive
+    pub fn category(&self, name: &str) -> Option<&CifCategory<'a>> {
+        self.categories.get(&name.to_ascii_lowercase())
+    }
+}
+
+impl<'a> CifCategory<'a> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// number of rows
+    pub fn len(&self) -> usize {
+        if self.items.is_empty() {
+            0
+        } else {
+            self.values.len() / self.items.len()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// value of `item` in `row`. Item names are case insensitive. Returns `None` for
+    /// missing items and for `.` and `?` values
+    pub fn get(&self, row: usize, item: &str) -> Option<&'a str> {
+        let column = self
+            .items
+            .iter()
+            .position(|i| i.eq_ignore_ascii_case(item))?;
+        self.values.get(row * self.items.len() + column)?.1
+    }
+
+    /// offset and text of the first of `items` having a value in `row`
+    fn lookup(&self, row: usize, items: &[&str]) -> Option<(usize, &'a str)> {
+        items.iter().find_map(|item| {
+            let column = self
+                .items
+                .iter()
+                .position(|i| i.eq_ignore_ascii_case(item))?;
+            let (offset, value) = self.values.get(row * self.items.len() + column)?;
+            value.map(|v| (*offset, v))
+        })
+    }
+
+    /// first character of the first of `items` having a value in `row`
+    fn character(&self, row: usize, items: &[&str]) -> Option<char> {
+        self.lookup(row, items).and_then(|(_, v)| v.chars().next())
+    }
+
+    /// first of `items` having a value in `row` parsed as `T`
+    fn parsed<T: FromStr>(
+        &self,
+        input: &str,
+        row: usize,
+        items: &[&str],
+    ) -> Result<Option<T>, PdbParseError> {
+        match self.lookup(row, items) {
+            Some((offset, value)) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| self.error(input, offset, format!("invalid value of {}", items[0]))),
+            None => Ok(None),
+        }
+    }
+
+    /// same as `parsed` but fails if none of `items` has a value
+    fn required<T: FromStr>(
+        &self,
+        input: &str,
+        row: usize,
+        items: &[&str],
+    ) -> Result<T, PdbParseError> {
+        self.parsed(input, row, items)?
+            .ok_or_else(|| self.error(input, self.offset, format!("missing {}", items[0])))
+    }
+
+    /// chain identifier given by the first of `items` having a value in `row`. Blank if
+    /// none is given, fails for identifiers longer than a single character
+    fn chain_id(&self, input: &str, row: usize, items: &[&str]) -> Result<char, PdbParseError> {
+        match self.lookup(row, items) {
+            None => Ok(' '),
+            Some((offset, value)) => {
+                let mut chars = value.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(c),
+                    _ => Err(self.error(
+                        input,
+                        offset,
+                        "chain identifier does not fit a single character".to_owned(),
+                    )),
+                }
+            }
+        }
+    }
+
+    fn error(&self, input: &str, offset: usize, reason: String) -> PdbParseError {
+        cif_error(input, offset, &self.name, reason)
+    }
+}
+
+fn cif_error(input: &str, offset: usize, record_name: &str, reason: String) -> PdbParseError {
+    let bytes = input.as_bytes();
+    PdbParseError {
+        record_name: record_name.to_owned(),
+        ..PdbParseError::at(bytes, &bytes[offset..], reason)
+    }
+}
+
+/// Splits `input` into CIF tokens paired with their byte offsets. Comments are skipped.
+/// Quoted values end at a matching quote followed by whitespace, text fields are
+/// delimited by semicolons at the start of a line and are trimmed.
+pub fn cif_tokens(input: &str) -> Result<Vec<(usize, CifToken<'_>)>, PdbParseError> {
+    let bytes = input.as_bytes();
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    while position < bytes.len() {
+        let start = position;
+        let current = bytes[start];
+        if current.is_ascii_whitespace() {
+            position += 1;
+        } else if current == b'#' {
+            position = bytes[start..]
+                .iter()
+                .position(|c| *c == b'\n')
+                .map_or(bytes.len(), |p| start + p);
+        } else if current == b';' && (start == 0 || bytes[start - 1] == b'\n') {
+            let close = input[start..]
+                .find("\n;")
+                .map(|p| start + p)
+                .ok_or_else(|| cif_error(input, start, "", "unterminated text field".to_owned()))?;
+            tokens.push((start, CifToken::Value(Some(input[start + 1..close].trim()))));
+            position = close + 2;
+        } else if current == b'\'' || current == b'"' {
+            let mut close = start + 1;
+            loop {
+                match bytes.get(close) {
+                    None | Some(b'\n') | Some(b'\r') => {
+                        return Err(cif_error(
+                            input,
+                            start,
+                            "",
+                            "unterminated quoted value".to_owned(),
+                        ))
+                    }
+                    Some(c)
+                        if *c == current
+                            && bytes
+                                .get(close + 1)
+                                .copied()
+                                .unwrap_or(b' ')
+                                .is_ascii_whitespace() =>
+                    {
+                        break
+                    }
+                    _ => close += 1,
+                }
+            }
+            tokens.push((start, CifToken::Value(Some(&input[start + 1..close]))));
+            position = close + 1;
+        } else {
+            position = bytes[start..]
+                .iter()
+                .position(u8::is_ascii_whitespace)
+                .map_or(bytes.len(), |p| start + p);
+            let word = &input[start..position];
+            let token = match word {
+                "." | "?" => CifToken::Value(None),
+                _ if word.starts_with('_') => CifToken::Tag(word),
+                _ if word.eq_ignore_ascii_case("loop_") => CifToken::Loop,
+                _ if word
+                    .get(..5)
+                    .unwrap_or_default()
+                    .eq_ignore_ascii_case("data_") =>
+                {
+                    CifToken::DataBlock(&word[5..])
+                }
+                _ => CifToken::Value(Some(word)),
+            };
+            tokens.push((start, token));
+        }
+    }
+    Ok(tokens)
+}
+
+/// splits a data name into lowercase category and item names
+fn split_tag(input: &str, offset: usize, tag: &str) -> Result<(String, String), PdbParseError> {
+    let name = &tag[1..];
+    match name.find('.').map(|p| (&name[..p], &name[p + 1..])) {
+        Some((category, item)) if !category.is_empty() && !item.is_empty() => {
+            Ok((category.to_ascii_lowercase(), item.to_ascii_lowercase()))
+        }
+        _ => Err(cif_error(
+            input,
+            offset,
+            "",
+            "data name is not of the form _category.item".to_owned(),
+        )),
+    }
+}
+
+/// Reads the first data block of `input` into category tables. Following data blocks
+/// are ignored.
+pub fn cif_block(input: &str) -> Result<CifBlock<'_>, PdbParseError> {
+    let mut tokens = cif_tokens(input)?.into_iter().peekable();
+    let name = match tokens.next() {
+        Some((_, CifToken::DataBlock(name))) => name,
+        Some((offset, _)) => {
+            return Err(cif_error(
+                input,
+                offset,
+                "",
+                "expected data block header".to_owned(),
+            ))
+        }
+        None => return Err(cif_error(input, 0, "", "missing data block".to_owned())),
+    };
+    let mut block = CifBlock {
+        name,
+        categories: HashMap::new(),
+    };
+    while let Some((offset, token)) = tokens.next() {
+        match token {
+            CifToken::DataBlock(_) => break,
+            CifToken::Tag(tag) => {
+                let (category, item) = split_tag(input, offset, tag)?;
+                let value = match tokens.next() {
+                    Some((value_offset, CifToken::Value(value))) => (value_offset, value),
+                    _ => {
+                        return Err(cif_error(
+                            input,
+                            offset,
+                            &category,
+                            format!("missing value of {}", tag),
+                        ))
+                    }
+                };
+                let table =
+                    block
+                        .categories
+                        .entry(category.clone())
+                        .or_insert_with(|| CifCategory {
+                            name: category.clone(),
+                            offset,
+                            ..Default::default()
+                        });
+                if table.values.len() != table.items.len() {
+                    return Err(cif_error(
+                        input,
+                        offset,
+                        &category,
+                        "category is given both in a loop and as single items".to_owned(),
+                    ));
+                }
+                table.items.push(item);
+                table.values.push(value);
+            }
+            CifToken::Loop => {
+                let mut table: Option<CifCategory> = None;
+                while let Some((tag_offset, CifToken::Tag(tag))) = tokens.peek().copied() {
+                    tokens.next();
+                    let (category, item) = split_tag(input, tag_offset, tag)?;
+                    let table = table.get_or_insert_with(|| CifCategory {
+                        name: category.clone(),
+                        offset: tag_offset,
+                        ..Default::default()
+                    });
+                    if table.name != category {
+                        return Err(cif_error(
+                            input,
+                            tag_offset,
+                            &category,
+                            "loop contains items of different categories".to_owned(),
+                        ));
+                    }
+                    table.items.push(item);
+                }
+                let mut table = table.ok_or_else(|| {
+                    cif_error(input, offset, "", "loop without data names".to_owned())
+                })?;
+                while let Some((value_offset, CifToken::Value(value))) = tokens.peek().copied() {
+                    tokens.next();
+                    table.values.push((value_offset, value));
+                }
+                if table.values.len() % table.items.len() != 0 {
+                    return Err(table.error(
+                        input,
+                        table.offset,
+                        "number of values is not a multiple of the number of data names".to_owned(),
+                    ));
+                }
+                if block.categories.contains_key(&table.name) {
+                    return Err(table.error(
+                        input,
+                        table.offset,
+                        "category is given more than once".to_owned(),
+                    ));
+                }
+                block.categories.insert(table.name.clone(), table);
+            }
+            CifToken::Value(_) => {
+                return Err(cif_error(
+                    input,
+                    offset,
+                    "",
+                    "value without data name".to_owned(),
+                ))
+            }
+        }
+    }
+    Ok(block)
+}
+
+/// Parses a PDBx/mmCIF file into the records of the PDB format. Fails with a
+/// [PdbParseError](error/struct.PdbParseError.html) carrying the category name as
+/// record name if the file is not valid CIF or a mapped item has an invalid value.
+/// Rows which can not be represented in the PDB format, such as rows with chain
+/// identifiers longer than a single character or unknown experimental methods, are
+/// skipped and reported as diagnostics as in [parse_lenient](fn.parse_lenient.html).
+pub fn parse_cif(s: &str) -> Result<(PdbFile<Vec<Record>>, Vec<PdbParseError>), PdbParseError> {
+    let block = cif_block(s)?;
+    let id_code = block
+        .category("entry")
+        .and_then(|c| c.get(0, "id"))
+        .unwrap_or(block.name);
+    let mut records = Vec::new();
+    let mut diagnostics = Vec::new();
+    title_records(s, &block, id_code, &mut records, &mut diagnostics)?;
+    journal_records(&block, &mut records);
+    dbref_records(s, &block, id_code, &mut records, &mut diagnostics)?;
+    modres_records(s, &block, id_code, &mut records, &mut diagnostics)?;
+    atom_records(s, &block, &mut records, &mut diagnostics)?;
+    Ok((records.to_pdb_file(), diagnostics))
+}
+
+/// joins lines of multi-line values
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// converts `Last, F.M.` form of mmCIF names to `F.M.LAST` form of PDB records
+fn pdb_author_name(name: &str) -> String {
+    match name.find(',').map(|p| (&name[..p], &name[p + 1..])) {
+        Some((last, initials)) => format!("{}{}", initials.trim(), last.trim()),
+        None => name.trim().to_owned(),
+    }
+    .to_uppercase()
+}
+
+/// HEADER, TITLE, KEYWDS, EXPDTA and AUTHOR records. Unknown experimental methods
+/// are left out and reported
+fn title_records(
+    input: &str,
+    block: &CifBlock,
+    id_code: &str,
+    records: &mut Vec<Record>,
+    diagnostics: &mut Vec<PdbParseError>,
+) -> Result<(), PdbParseError> {
+    let keywords = block.category("struct_keywords");
+    let mut header = Header {
+        classification: keywords
+            .and_then(|c| c.get(0, "pdbx_keywords"))
+            .unwrap_or_default()
+            .to_owned(),
+        id_code: id_code.to_owned(),
+        ..Default::default()
+    };
+    if let Some(status) = block.category("pdbx_database_status") {
+        if let Some(date) =
+            status.parsed::<NaiveDate>(input, 0, &["recvd_initial_deposition_date"])?
+        {
+            header.deposition_date = date;
+        }
+    }
+    records.push(Record::Header(header));
+
+    if let Some(title) = block.category("struct").and_then(|c| c.get(0, "title")) {
+        records.push(Record::Title(Title {
+            title: single_line(title),
+        }));
+    }
+    if let Some(text) = keywords.and_then(|c| c.get(0, "text")) {
+        records.push(Record::Keywds(Keywds {
+            keywords: text
+                .split(',')
+                .map(single_line)
+                .filter(|k| !k.is_empty())
+                .collect(),
+        }));
+    }
+    if let Some(exptl) = block.category("exptl") {
+        let mut techniques = Vec::new();
+        for row in 0..exptl.len() {
+            match exptl.parsed(input, row, &["method"]) {
+                Ok(Some(technique)) => techniques.push(technique),
+                Ok(None) => {}
+                Err(e) => diagnostics.push(e),
+            }
+        }
+        records.push(Record::Experimental(Experimental { techniques }));
+    }
+    if let Some(authors) = block.category("audit_author") {
+        records.push(Record::Authors(Authors {
+            authors: (0..authors.len())
+                .filter_map(|row| authors.get(row, "name"))
+                .map(|name| Author(pdb_author_name(name)))
+                .collect(),
+        }));
+    }
+    Ok(())
+}
+
+/// JRNL records of the primary citation. Non numeric volumes and pages, such as
+/// electronic page numbers, are left out
+fn journal_records(block: &CifBlock, records: &mut Vec<Record>) {
+    let citation = match block.category("citation") {
+        Some(c) if !c.is_empty() => c,
+        _ => return,
+    };
+    let row = (0..citation.len())
+        .find(|row| citation.get(*row, "id") == Some("primary"))
+        .unwrap_or(0);
+    let id = citation.get(row, "id");
+
+    if let Some(authors) = block.category("citation_author") {
+        let authors: Vec<Author> = (0..authors.len())
+            .filter(|r| authors.get(*r, "citation_id") == id)
+            .filter_map(|r| authors.get(r, "name"))
+            .map(|name| Author(pdb_author_name(name)))
+            .collect();
+        if !authors.is_empty() {
+            records.push(Record::JournalAuthors(JournalAuthors { authors }));
+        }
+    }
+    if let Some(title) = citation.get(row, "title") {
+        records.push(Record::JournalTitle(JournalTitle {
+            title: single_line(title),
+        }));
+    }
+    if let Some(publication_name) = citation.get(row, "journal_abbrev") {
+        let number = |item| citation.get(row, item).and_then(|v| v.parse().ok());
+        records.push(Record::JournalReference(JournalReference {
+            publication_name: publication_name.to_owned(),
+            volume: number("journal_volume"),
+            page: number("page_first"),
+            year: number("year"),
+        }));
+    }
+    if let Some(issn) = citation.get(row, "journal_id_issn") {
+        records.push(Record::JournalCitation(JournalCitation {
+            serial_type: Some(SerialNumber::Issn),
+            serial: Some(issn.to_owned()),
+        }));
+    }
+    if let Some(id) = citation
+        .get(row, "pdbx_database_id_pubmed")
+        .and_then(|v| v.parse().ok())
+    {
+        records.push(Record::JournalPubMedId(JournalPubMedId { id }));
+    }
+    if let Some(doi) = citation.get(row, "pdbx_database_id_doi") {
+        records.push(Record::JournalDoi(JournalDoi { id: doi.to_owned() }));
+    }
+}
+
+/// DBREF records of struct_ref_seq rows joined with struct_ref rows by reference id
+fn dbref_records(
+    input: &str,
+    block: &CifBlock,
+    id_code: &str,
+    records: &mut Vec<Record>,
+    diagnostics: &mut Vec<PdbParseError>,
+) -> Result<(), PdbParseError> {
+    let (references, sequences) = match (
+        block.category("struct_ref"),
+        block.category("struct_ref_seq"),
+    ) {
+        (Some(r), Some(s)) => (r, s),
+        _ => return Ok(()),
+    };
+    for row in 0..sequences.len() {
+        let reference = (0..references.len())
+            .find(|r| references.get(*r, "id") == sequences.get(row, "ref_id"))
+            .ok_or_else(|| {
+                let offset = sequences
+                    .lookup(row, &["ref_id"])
+                    .map_or(sequences.offset, |(offset, _)| offset);
+                sequences.error(input, offset, "unknown struct_ref id".to_owned())
+            })?;
+        let chain_id = match sequences.chain_id(input, row, &["pdbx_strand_id"]) {
+            Ok(c) => c,
+            Err(e) => {
+                diagnostics.push(e);
+                continue;
+            }
+        };
+        records.push(Record::Dbref(Dbref {
+            idcode: sequences
+                .get(row, "pdbx_pdb_id_code")
+                .unwrap_or(id_code)
+                .to_owned(),
+            chain_id,
+            seq_begin: sequences.required(
+                input,
+                row,
+                &["pdbx_auth_seq_align_beg", "seq_align_beg"],
+            )?,
+            initial_sequence: sequences.character(row, &["pdbx_seq_align_beg_ins_code"]),
+            seq_end: sequences.required(
+                input,
+                row,
+                &["pdbx_auth_seq_align_end", "seq_align_end"],
+            )?,
+            ending_sequence: sequences.character(row, &["pdbx_seq_align_end_ins_code"]),
+            database: references
+                .get(reference, "db_name")
+                .unwrap_or_default()
+                .to_owned(),
+            db_accession: sequences
+                .get(row, "pdbx_db_accession")
+                .or_else(|| references.get(reference, "pdbx_db_accession"))
+                .unwrap_or_default()
+                .to_owned(),
+            db_idcode: references
+                .get(reference, "db_code")
+                .unwrap_or_default()
+                .to_owned(),
+            db_seq_begin: sequences.required(input, row, &["db_align_beg"])?,
+            idbns_begin: sequences.character(row, &["pdbx_db_align_beg_ins_code"]),
+            db_seq_end: sequences.required(input, row, &["db_align_end"])?,
+            dbins_end: sequences.character(row, &["pdbx_db_align_end_ins_code"]),
+        }));
+    }
+    Ok(())
+}
+
+/// MODRES records of pdbx_struct_mod_residue rows
+fn modres_records(
+    input: &str,
+    block: &CifBlock,
+    id_code: &str,
+    records: &mut Vec<Record>,
+    diagnostics: &mut Vec<PdbParseError>,
+) -> Result<(), PdbParseError> {
+    let residues = match block.category("pdbx_struct_mod_residue") {
+        Some(r) => r,
+        None => return Ok(()),
+    };
+    for row in 0..residues.len() {
+        let chain_id = match residues.chain_id(input, row, &["auth_asym_id", "label_asym_id"]) {
+            Ok(c) => c,
+            Err(e) => {
+                diagnostics.push(e);
+                continue;
+            }
+        };
+        records.push(Record::Modres(Modres {
+            idcode: id_code.to_owned(),
+            residue_name: residues.required(input, row, &["auth_comp_id", "label_comp_id"])?,
+            chain_id,
+            sequence_number: residues.required(input, row, &["auth_seq_id", "label_seq_id"])?,
+            insertion_code: residues.character(row, &["pdb_ins_code"]),
+            standart_residue_name: residues
+                .get(row, "parent_comp_id")
+                .unwrap_or_default()
+                .to_owned(),
+            comment: residues.get(row, "details").unwrap_or_default().to_owned(),
+        }));
+    }
+    Ok(())
+}
+
+/// ATOM and HETATM records of atom_site rows. Author provided names and numbers are
+/// preferred over label ones as in PDB files. Models are enclosed in MODEL and ENDMDL
+/// records only if there are more than one
+fn atom_records(
+    input: &str,
+    block: &CifBlock,
+    records: &mut Vec<Record>,
+    diagnostics: &mut Vec<PdbParseError>,
+) -> Result<(), PdbParseError> {
+    let sites = match block.category("atom_site") {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+    let multiple_models = (0..sites.len())
+        .any(|row| sites.get(row, "pdbx_pdb_model_num") != sites.get(0, "pdbx_pdb_model_num"));
+    let mut current_model = None;
+    for row in 0..sites.len() {
+        if multiple_models {
+            let model = sites.required(input, row, &["pdbx_pdb_model_num"])?;
+            if current_model != Some(model) {
+                if current_model.is_some() {
+                    records.push(Record::Endmdl);
+                }
+                records.push(Record::Model(Model { serial: model }));
+                current_model = Some(model);
+            }
+        }
+        let chain_id = match sites.chain_id(input, row, &["auth_asym_id", "label_asym_id"]) {
+            Ok(c) => c,
+            Err(e) => {
+                diagnostics.push(e);
+                continue;
+            }
+        };
+        let atom = Atom {
+            serial: sites.required(input, row, &["id"])?,
+            name: sites.required(input, row, &["auth_atom_id", "label_atom_id"])?,
+            alt_loc: sites.character(row, &["label_alt_id"]),
+            residue_name: sites.required(input, row, &["auth_comp_id", "label_comp_id"])?,
+            chain_id,
+            sequence_number: sites.required(input, row, &["auth_seq_id", "label_seq_id"])?,
+            insertion_code: sites.character(row, &["pdbx_pdb_ins_code"]),
+            x: sites.required(input, row, &["cartn_x"])?,
+            y: sites.required(input, row, &["cartn_y"])?,
+            z: sites.required(input, row, &["cartn_z"])?,
+            occupancy: sites.required(input, row, &["occupancy"])?,
+            temp_factor: sites.required(input, row, &["b_iso_or_equiv"])?,
+            element: sites.get(row, "type_symbol").map(str::to_owned),
+            charge: sites
+                .parsed(input, row, &["pdbx_formal_charge"])?
+                .filter(|c| *c != 0),
+        };
+        records.push(match sites.get(row, "group_pdb") {
+            Some("HETATM") => Record::Hetatm(atom),
+            _ => Record::Atom(atom),
+        });
+    }
+    if current_model.is_some() {
+        records.push(Record::Endmdl);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::record::test::{get_test_file_path, read_file};
+
+    #[test]
+    fn tokens() {
+        let text =
+            "data_1ABC\n# comment\nloop_\n_a.b 'it''s' \"x y\" . ?\n;line one\nline two\n;\n";
+        let tokens: Vec<CifToken> = cif_tokens(text)
+            .unwrap()
+            .into_iter()
+            .map(|(_, t)| t)
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                CifToken::DataBlock("1ABC"),
+                CifToken::Loop,
+                CifToken::Tag("_a.b"),
+                CifToken::Value(Some("it''s")),
+                CifToken::Value(Some("x y")),
+                CifToken::Value(None),
+                CifToken::Value(None),
+                CifToken::Value(Some("line one\nline two")),
+            ]
+        );
+        let error = cif_tokens("data_x\n_a.b 'open\n").unwrap_err();
+        assert_eq!(error.line_number, 2);
+        assert_eq!(error.columns.0, 6);
+    }
+
+    #[test]
+    fn block() {
+        let text = r#"data_1ABC
+_entry.id 1ABC
+loop_
+_atom_site.id
+_atom_site.Cartn_x
+1 10.5
+2 ?
+"#;
+        let block = cif_block(text).unwrap();
+        assert_eq!(block.name, "1ABC");
+        assert_eq!(block.category("entry").unwrap().get(0, "id"), Some("1ABC"));
+        let sites = block.category("ATOM_SITE").unwrap();
+        assert_eq!(sites.len(), 2);
+        assert_eq!(sites.get(0, "cartn_x"), Some("10.5"));
+        assert_eq!(sites.get(1, "Cartn_x"), None);
+
+        let error = cif_block("data_x\nloop_\n_a.b\n_a.c\n1 2 3\n").unwrap_err();
+        assert_eq!(error.record_name, "a");
+        assert_eq!(error.line_number, 3);
+    }
+
+    #[test]
+    fn models() {
+        let text = r#"data_2XYZ
+loop_
+_atom_site.group_PDB
+_atom_site.id
+_atom_site.type_symbol
+_atom_site.label_atom_id
+_atom_site.label_alt_id
+_atom_site.label_comp_id
+_atom_site.label_asym_id
+_atom_site.label_seq_id
+_atom_site.pdbx_PDB_ins_code
+_atom_site.Cartn_x
+_atom_site.Cartn_y
+_atom_site.Cartn_z
+_atom_site.occupancy
+_atom_site.B_iso_or_equiv
+_atom_site.pdbx_formal_charge
+_atom_site.auth_seq_id
+_atom_site.auth_asym_id
+_atom_site.pdbx_PDB_model_num
+ATOM   1 N N   . MET A 1 ? 1.000 2.000 3.000 1.00 0.00 0 10 B 1
+HETATM 2 O O   . HOH B . ? 4.000 5.000 6.000 0.50 9.00 0 20 B 1
+ATOM   3 N N   . MET A 1 ? 1.500 2.500 3.500 1.00 0.00 1 10 B 2
+"#;
+        let (pdb, diagnostics) = parse_cif(text).unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(pdb.header().header().unwrap().id_code, "2XYZ");
+        let records = pdb.records();
+        assert_eq!(records.len(), 8);
+        assert!(matches!(records[1], Record::Model(Model { serial: 1 })));
+        assert!(matches!(records[4], Record::Endmdl));
+        assert!(matches!(records[7], Record::Endmdl));
+        if let Record::Hetatm(water) = &records[3] {
+            assert_eq!(water.residue_name, "HOH");
+            assert_eq!(water.chain_id, 'B');
+            assert_eq!(water.sequence_number, 20);
+            assert_eq!(water.occupancy, 0.5);
+            assert_eq!(water.charge, None);
+        } else {
+            panic!()
+        }
+        if let Record::Atom(nitrogen) = &records[6] {
+            assert_eq!(nitrogen.serial, 3);
+            assert_eq!(nitrogen.x, 1.5);
+            assert_eq!(nitrogen.charge, Some(1));
+        } else {
+            panic!()
+        }
+
+        let (pdb, diagnostics) = parse_cif(&text.replace("20 B 1", "20 BB 1")).unwrap();
+        assert_eq!(pdb.records().len(), 7);
+        assert!(pdb
+            .records()
+            .iter()
+            .all(|r| !matches!(r, Record::Hetatm(_))));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].record_name, "atom_site");
+        assert_eq!(diagnostics[0].line_number, 22);
+
+        let error = parse_cif(&text.replace("4.000", "4.0x0")).err().unwrap();
+        assert_eq!(error.record_name, "atom_site");
+        assert_eq!(error.line_number, 22);
+    }
+
+    #[test]
+    fn cif_header() {
+        let (cif, diagnostics) = parse_cif(
+            r#"data_1BYI
+#
+_entry.id   1BYI
+#
+_pdbx_database_status.entry_id                        1BYI
+_pdbx_database_status.recvd_initial_deposition_date   1998-10-15
+#
+_struct.entry_id   1BYI
+_struct.title      'STRUCTURE OF APO-DETHIOBIOTIN SYNTHASE AT 0.97 ANGSTROMS RESOLUTION'
+#
+_struct_keywords.entry_id        1BYI
+_struct_keywords.pdbx_keywords   LIGASE
+_struct_keywords.text            'BIOTIN SYNTHESIS, CYCLO-LIGASE, LIGASE'
+#
+_exptl.entry_id   1BYI
+_exptl.method     'X-RAY DIFFRACTION'
+#
+loop_
+_audit_author.name
+_audit_author.pdbx_ordinal
+'Sandalova, T.'  1
+'Schneider, G.'  2
+'Kaeck, H.'      3
+'Lindqvist, Y.'  4
+#
+_citation.id                        primary
+_citation.title
+;Structure of dethiobiotin synthetase at 0.97 A
+resolution.
+;
+_citation.journal_abbrev            'Acta Crystallogr.,Sect.D'
+_citation.journal_volume            55
+_citation.page_first                610
+_citation.year                      1999
+_citation.journal_id_ISSN           0907-4449
+_citation.pdbx_database_id_PubMed   10089457
+_citation.pdbx_database_id_DOI      10.1107/S090744499801381X
+#
+loop_
+_citation_author.citation_id
+_citation_author.name
+_citation_author.ordinal
+primary 'Sandalova, T.' 1
+primary 'Schneider, G.' 2
+primary 'Kack, H.'      3
+primary 'Lindqvist, Y.' 4
+#
+_struct_ref.id                1
+_struct_ref.db_name           UNP
+_struct_ref.db_code           BIOD_ECOLI
+_struct_ref.pdbx_db_accession P13000
+#
+_struct_ref_seq.align_id                 1
+_struct_ref_seq.ref_id                   1
+_struct_ref_seq.pdbx_PDB_id_code         1BYI
+_struct_ref_seq.pdbx_strand_id           A
+_struct_ref_seq.seq_align_beg            1
+_struct_ref_seq.seq_align_end            224
+_struct_ref_seq.db_align_beg             1
+_struct_ref_seq.db_align_end             224
+_struct_ref_seq.pdbx_auth_seq_align_beg  1
+_struct_ref_seq.pdbx_auth_seq_align_end  224
+#
+"#,
+        )
+        .unwrap();
+        assert!(diagnostics.is_empty());
+        let contents = read_file(&get_test_file_path("1BYI.pdb"));
+        let pdb = crate::parse_strict(&contents).unwrap();
+
+        let (header, expected) = (cif.header(), pdb.header());
+        let (entry, expected_entry) = (header.header().unwrap(), expected.header().unwrap());
+        assert_eq!(entry.id_code, expected_entry.id_code);
+        assert_eq!(entry.classification, expected_entry.classification);
+        // PDB files carry two digit years, compare the dates as they are written
+        assert_eq!(
+            crate::writer::format_date(&entry.deposition_date),
+            crate::writer::format_date(&expected_entry.deposition_date)
+        );
+        assert_eq!(
+            header.title().unwrap().title,
+            expected.title().unwrap().title
+        );
+        assert_eq!(
+            header.keywds().unwrap().keywords,
+            expected.keywds().unwrap().keywords
+        );
+        assert_eq!(
+            header.expdta().unwrap().techniques,
+            expected.expdta().unwrap().techniques
+        );
+        assert_eq!(
+            header.authors().unwrap().authors,
+            expected.authors().unwrap().authors
+        );
+
+        let (journal, expected) = (header.journal(), expected.journal());
+        assert_eq!(
+            journal.authors().unwrap().authors,
+            expected.authors().unwrap().authors
+        );
+        assert_eq!(
+            journal.title().unwrap().title.to_uppercase(),
+            expected.title().unwrap().title
+        );
+        let (reference, expected_reference) =
+            (journal.reference().unwrap(), expected.reference().unwrap());
+        assert_eq!(
+            reference.publication_name.to_uppercase(),
+            expected_reference.publication_name
+        );
+        assert_eq!(reference.volume, expected_reference.volume);
+        assert_eq!(reference.page, expected_reference.page);
+        assert_eq!(reference.year, expected_reference.year);
+        assert_eq!(
+            journal.citation().unwrap().serial,
+            expected.citation().unwrap().serial
+        );
+        assert_eq!(
+            journal.pubmedid().unwrap().id,
+            expected.pubmedid().unwrap().id
+        );
+        assert_eq!(journal.doi().unwrap().id, expected.doi().unwrap().id);
+
+        let dbref = cif.primary().dbreference().unwrap();
+        let expected = pdb.primary().dbreference().unwrap();
+        assert_eq!(
+            (
+                dbref.idcode.as_str(),
+                dbref.chain_id,
+                dbref.seq_begin,
+                dbref.seq_end
+            ),
+            (
+                expected.idcode.as_str(),
+                expected.chain_id,
+                expected.seq_begin,
+                expected.seq_end
+            )
+        );
+        assert_eq!(
+            (&dbref.database, &dbref.db_accession, &dbref.db_idcode),
+            (
+                &expected.database,
+                &expected.db_accession,
+                &expected.db_idcode
+            )
+        );
+        assert_eq!(
+            (dbref.db_seq_begin, dbref.db_seq_end),
+            (expected.db_seq_begin, expected.db_seq_end)
+        );
+    }
+
+    #[test]
+    fn unknown_method() {
+        let text = r#"data_3XYZ
+loop_
+_exptl.entry_id
+_exptl.method
+3XYZ 'X-RAY DIFFRACTION'
+3XYZ 'NEUTRON IMAGING'
+"#;
+        let (pdb, diagnostics) = parse_cif(text).unwrap();
+        assert_eq!(
+            pdb.header().expdta().unwrap().techniques,
+            vec![ExperimentalTechnique::XRayDiffraction]
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].record_name, "exptl");
+        assert_eq!(diagnostics[0].line_number, 6);
+    }
+}
@@ -0,0 +1,83 @@
+/*!
+Contains parsers related to [Cispep](http://www.wwpdb.org/documentation/file-format-content/format33/sect6.html#CISPEP) records.
+CISPEP records specify the prolines and other peptides found to be in the cis conformation.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{character::complete::line_ending, do_parse, map_res, named, take, value};
+
+named!(
+    #[doc=r#"Parses CISPEP record. If successfull returns [Record](../ast/types/enum.Record.html) variant
+containing [Cispep](../ast/types/struct.Cispep.html) instance. Blank model and measure columns
+read as zero, a value that does not parse fails the record.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD       | DEFINITION                                         |
+|----------|--------------|-------------|----------------------------------------------------|
+| 1 -  6   | Record name  | "CISPEP"    |                                                    |
+| 8 - 10   | Integer      | serNum      | Record serial number.                              |
+| 12 - 14  | LString(3)   | pep1        | Residue name.                                      |
+| 16       | Character    | chainID1    | Chain identifier.                                  |
+| 18 - 21  | Integer      | seqNum1     | Residue sequence number.                           |
+| 22       | AChar        | icode1      | Insertion code.                                    |
+| 26 - 28  | LString(3)   | pep2        | Residue name.                                      |
+| 30       | Character    | chainID2    | Chain identifier.                                  |
+| 32 - 35  | Integer      | seqNum2     | Residue sequence number.                           |
+| 36       | AChar        | icode2      | Insertion code.                                    |
+| 44 - 46  | Integer      | modNum      | Identifies the specific model.                     |
+| 54 - 59  | Real(6.2)    | measure     | Angle measurement in degrees.                      |
+"#],
+    pub cispep_record_parser<Record>,
+    do_parse!(
+        cispep
+            >> take!(1)
+            >> serial: threedigit_integer
+            >> take!(1)
+            >> residue1: spaced_residue_reference
+            >> take!(3)
+            >> residue2: spaced_residue_reference
+            >> rest: till_line_ending
+            >> model: map_res!(value!(rest), |r| parsed_column(r, 7, 10))
+            >> omega: map_res!(value!(rest), |r| parsed_column(r, 17, 23))
+            >> line_ending
+            >> (Record::Cispep(Cispep {
+                serial,
+                residue1,
+                residue2,
+                model: model.unwrap_or_default(),
+                omega: omega.unwrap_or_default(),
+            }))
+    )
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cispep() {
+        if let Ok((_, Record::Cispep(res))) = cispep_record_parser(
+            "CISPEP   2 GLY A  314    PRO A  315          0        -4.15                     \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.serial, 2);
+            assert_eq!(res.residue1.residue_name, "GLY");
+            assert_eq!(res.residue1.sequence_number, 314);
+            assert_eq!(res.residue2.residue_name, "PRO");
+            assert_eq!(res.residue2.chain_id, 'A');
+            assert_eq!(res.model, 0);
+            assert_eq!(res.omega, -4.15);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn cispep_malformed_measure() {
+        assert!(cispep_record_parser(
+            "CISPEP   2 GLY A  314    PRO A  315          0        -4.x5                     \n"
+                .as_bytes(),
+        )
+        .is_err());
+    }
+}
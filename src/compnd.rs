@@ -268,7 +268,7 @@ make_token_parser!(
 make_token_parser!(
     r#"Parses tokens of the form "EXPRESSION_SYSTEM_TAXID: 1234". Returns [Token::ExpressionSystemTaxId](../ast/types/enum.Token.html)"#,
     expression_system_tax_id_parser,
-    expression_system_tax_id,
+    expression_system_taxid,
     integer_list,
     a,
     Token::ExpressionSystemTaxId { id: a }
@@ -453,7 +453,7 @@ named!(
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<CmpndLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
+                continuation: cont.unwrap_or_default(),
                 remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
                 phantom: PhantomData,
             })
@@ -493,7 +493,7 @@ mod test {
         if let Ok((_, Token::MoleculeId(res))) = super::mol_id_parser("MOL_ID:  1".as_bytes()) {
             assert_eq!(res, 1);
         } else {
-            assert!(false);
+            panic!();
         }
     }
 
@@ -504,7 +504,18 @@ mod test {
         {
             assert_eq!(name, "HEMOGLOBIN ALPHA CHAIN");
         } else {
-            assert!(false);
+            panic!();
+        }
+    }
+
+    #[test]
+    fn expression_system_tax_id_parser() {
+        if let Ok((_, Token::ExpressionSystemTaxId { id })) =
+            super::expression_system_tax_id_parser("EXPRESSION_SYSTEM_TAXID: 562".as_bytes())
+        {
+            assert_eq!(id, vec![562]);
+        } else {
+            panic!();
         }
     }
 
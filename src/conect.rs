@@ -0,0 +1,108 @@
+/*!
+Contains parsers related to [Conect](http://www.wwpdb.org/documentation/file-format-content/format33/sect10.html#CONECT)
+records. The CONECT records specify connectivity between atoms for which coordinates are supplied.
+Up to four bonded atoms are listed per line, atoms having more bonds are continued on the
+following CONECT lines of the same atom.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{character::complete::line_ending, do_parse, map_opt, named, IResult};
+use std::convert::TryFrom;
+
+pub struct ConectLine {
+    serial: u32,
+    bonded: Vec<u32>,
+}
+
+/// reads the bonded atom serial numbers of columns 12 - 31. Blank slots are skipped,
+/// returns `None` if a non-blank slot is not a hybrid-36 serial number
+fn bonded_atoms(rest: &[u8]) -> Option<Vec<u32>> {
+    (0..4)
+        .filter_map(|i| optional_column(rest, 5 * i, 5 * i + 5))
+        .map(|s| hybrid36_decode(5, s).and_then(|v| u32::try_from(v).ok()))
+        .collect()
+}
+
+named!(#[doc=r#"Parses a line of [CONECT](http://www.wwpdb.org/documentation/file-format-content/format33/sect10.html#CONECT) record.
+
+| COLUMNS  | DATA  TYPE   | FIELD       | DEFINITION                          |
+|----------|--------------|-------------|-------------------------------------|
+| 1 -  6   | Record name  | "CONECT"    |                                     |
+| 7 - 11   | Integer      | serial      | Atom  serial number                 |
+| 12 - 16  | Integer      | serial      | Serial number of bonded atom        |
+| 17 - 21  | Integer      | serial      | Serial number of bonded atom        |
+| 22 - 26  | Integer      | serial      | Serial number of bonded atom        |
+| 27 - 31  | Integer      | serial      | Serial number of bonded atom        |
+"#],
+    pub conect_line_parser<ConectLine>,
+    do_parse!(
+        conect
+            >> serial: fivedigit_hybrid36
+            >> bonded: map_opt!(till_line_ending, bonded_atoms)
+            >> line_ending
+            >> (ConectLine { serial, bonded })
+    )
+);
+
+/// Parses consecutive CONECT lines of a single atom. If successfull returns
+/// [Record](../ast/types/enum.Record.html) variant containing [Conect](../ast/types/struct.Conect.html)
+/// instance with the bonded atoms of all lines.
+pub fn conect_record_parser(s: &[u8]) -> IResult<&[u8], Record> {
+    let (mut rest, first) = conect_line_parser(s)?;
+    let mut bonded = first.bonded;
+    let mut source_lines = 1;
+    while let Ok((next, line)) = conect_line_parser(rest) {
+        if line.serial != first.serial {
+            break;
+        }
+        bonded.extend(line.bonded);
+        source_lines += 1;
+        rest = next;
+    }
+    Ok((
+        rest,
+        Record::Conect(Conect {
+            serial: first.serial,
+            bonded,
+            source_lines,
+        }),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn conect() {
+        let lines = r#"CONECT 1179  746 1184 1195 1203
+CONECT 1179 1211 1222
+CONECT 1021  544 1017 1020 1022
+"#;
+        if let Ok((rest, Record::Conect(res))) = conect_record_parser(lines.as_bytes()) {
+            assert_eq!(res.serial, 1179);
+            assert_eq!(res.bonded, vec![746, 1184, 1195, 1203, 1211, 1222]);
+            assert_eq!(res.source_lines, 2);
+            if let Ok((_, Record::Conect(next))) = conect_record_parser(rest) {
+                assert_eq!(next.serial, 1021);
+                assert_eq!(next.bonded.len(), 4);
+            } else {
+                panic!()
+            }
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn conect_malformed_serial() {
+        assert!(conect_record_parser("CONECT 1874   56   7x  142  187\n".as_bytes()).is_err());
+        if let Ok((rest, Record::Conect(res))) =
+            conect_record_parser("CONECT 1874   56  142\nCONECT 1874   7x\n".as_bytes())
+        {
+            assert_eq!(res.bonded, vec![56, 142]);
+            assert!(conect_record_parser(rest).is_err());
+        } else {
+            panic!()
+        }
+    }
+}
@@ -0,0 +1,77 @@
+/*!
+Contains parsers related to [Cryst1](http://www.wwpdb.org/documentation/file-format-content/format33/sect8.html#CRYST1) records.
+The CRYST1 record presents the unit cell parameters, space group, and Z value. If the structure was not
+determined by crystallographic means, CRYST1 simply provides the unitary values.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{character::complete::line_ending, do_parse, named};
+
+named!(
+    #[doc=r#"Parses CRYST1 record. If successfull returns [Record](../ast/types/enum.Record.html) variant
+containing [Cryst1](../ast/types/struct.Cryst1.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD       | DEFINITION                                         |
+|----------|--------------|-------------|----------------------------------------------------|
+| 1 -  6   | Record name  | "CRYST1"    |                                                    |
+| 7 - 15   | Real(9.3)    | a           | a (Angstroms).                                     |
+| 16 - 24  | Real(9.3)    | b           | b (Angstroms).                                     |
+| 25 - 33  | Real(9.3)    | c           | c (Angstroms).                                     |
+| 34 - 40  | Real(7.2)    | alpha       | alpha (degrees).                                   |
+| 41 - 47  | Real(7.2)    | beta        | beta (degrees).                                    |
+| 48 - 54  | Real(7.2)    | gamma       | gamma (degrees).                                   |
+| 56 - 66  | LString      | sGroup      | Space  group.                                      |
+| 67 - 70  | Integer      | z           | Z value.                                           |
+"#],
+    pub cryst1_record_parser<Record>,
+    do_parse!(
+        cryst1
+            >> a: ninedigit_real
+            >> b: ninedigit_real
+            >> c: ninedigit_real
+            >> alpha: sevendigit_real
+            >> beta: sevendigit_real
+            >> gamma: sevendigit_real
+            >> rest: till_line_ending
+            >> line_ending
+            >> (Record::Cryst1(Cryst1 {
+                a,
+                b,
+                c,
+                alpha,
+                beta,
+                gamma,
+                space_group: optional_column(rest, 1, 12).unwrap_or_default().to_owned(),
+                z: optional_column(rest, 12, 16)
+                    .and_then(|z| z.parse().ok())
+                    .unwrap_or_default(),
+            }))
+    )
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cryst1() {
+        if let Ok((_, Record::Cryst1(res))) = cryst1_record_parser(
+            "CRYST1   96.980   46.650   65.710  90.00 115.57  90.00 C 1 2 1       4          \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.a, 96.98);
+            assert_eq!(res.b, 46.65);
+            assert_eq!(res.c, 65.71);
+            assert_eq!(res.beta, 115.57);
+            assert_eq!(res.space_group, "C 1 2 1");
+            assert_eq!(res.z, 4);
+            let fractional = res.fractionalization_matrix();
+            assert!((fractional.rows[0][0] - 0.010311).abs() < 1e-6);
+            assert!((fractional.rows[0][2] - 0.004934).abs() < 1e-6);
+            assert!((fractional.rows[2][2] - 0.016871).abs() < 1e-6);
+        } else {
+            panic!()
+        }
+    }
+}
@@ -0,0 +1,168 @@
+/*!
+Contains error types reported by the top level parse functions.
+*/
+use std::{error::Error, fmt};
+
+/// Describes why and where a pdb file could not be parsed.
+/// Line numbers and columns are 1 based, columns are inclusive
+/// as in the column tables of the wwPDB format documentation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdbParseError {
+    pub line_number: usize,
+    pub record_name: String,
+    pub columns: (usize, usize),
+    pub reason: String,
+}
+
+impl PdbParseError {
+    /// builds an error pointing at `position` which is a suffix of `input`.
+    /// Column span starts at `position` and ends with the field found there
+    pub(crate) fn at(input: &[u8], position: &[u8], reason: String) -> PdbParseError {
+        let offset = input.len() - position.len();
+        let line_start = input[..offset]
+            .iter()
+            .rposition(|c| *c == b'\n')
+            .map_or(0, |p| p + 1);
+        let line = &input[line_start..];
+        let line = &line[..line
+            .iter()
+            .position(|c| *c == b'\r' || *c == b'\n')
+            .unwrap_or(line.len())];
+        let column = offset - line_start + 1;
+        let field = &line[(column - 1).min(line.len())..];
+        let leading_spaces = field.iter().take_while(|c| **c == b' ').count();
+        let width = leading_spaces
+            + field[leading_spaces..]
+                .iter()
+                .take_while(|c| **c != b' ')
+                .count();
+
+        PdbParseError {
+            line_number: input[..line_start].iter().filter(|c| **c == b'\n').count() + 1,
+            record_name: String::from_utf8_lossy(&line[..line.len().min(6)])
+                .trim()
+                .to_owned(),
+            columns: (column, column + width.max(1) - 1),
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for PdbParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, {} record, columns {}-{}: {}",
+            self.line_number, self.record_name, self.columns.0, self.columns.1, self.reason
+        )
+    }
+}
+
+impl Error for PdbParseError {}
+
+/// Reported when the number of residues listed in the SEQRES records
+/// of a chain differs from the numRes field of the records, or when
+/// the SEQRES records of a chain give different numRes values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeqresLengthError {
+    pub chain_id: char,
+    pub num_res: u32,
+    pub residues: usize,
+    /// numRes of a following SEQRES record differing from `num_res`
+    pub conflicting_num_res: Option<u32>,
+}
+
+impl fmt::Display for SeqresLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.conflicting_num_res {
+            Some(conflicting) => write!(
+                f,
+                "chain {}: numRes is {} but a following SEQRES record gives {}",
+                self.chain_id, self.num_res, conflicting
+            ),
+            None => write!(
+                f,
+                "chain {}: numRes is {} but {} residues are listed",
+                self.chain_id, self.num_res, self.residues
+            ),
+        }
+    }
+}
+
+impl Error for SeqresLengthError {}
+
+/// count fields of the MASTER record which can be recounted from the entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MasterField {
+    Remark,
+    Het,
+    Helix,
+    Sheet,
+    Site,
+    Xform,
+    Coord,
+    Ter,
+    Conect,
+    Seq,
+}
+
+impl fmt::Display for MasterField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            MasterField::Remark => "numRemark",
+            MasterField::Het => "numHet",
+            MasterField::Helix => "numHelix",
+            MasterField::Sheet => "numSheet",
+            MasterField::Site => "numSite",
+            MasterField::Xform => "numXform",
+            MasterField::Coord => "numCoord",
+            MasterField::Ter => "numTer",
+            MasterField::Conect => "numConect",
+            MasterField::Seq => "numSeq",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Reported when a record count given in the MASTER record differs from
+/// the number of records found in the entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MasterCountError {
+    pub field: MasterField,
+    pub master: u32,
+    pub counted: u32,
+}
+
+impl fmt::Display for MasterCountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} is {} in MASTER record but {} records are found",
+            self.field, self.master, self.counted
+        )
+    }
+}
+
+impl Error for MasterCountError {}
+
+/// Reported by the writer when a value does not fit into the columns of its
+/// field, serial and sequence numbers even when written as hybrid-36 numbers.
+/// Lines longer than 80 columns are reported with the `line` field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdbWriteError {
+    pub field: &'static str,
+    pub value: String,
+    pub width: usize,
+}
+
+impl fmt::Display for PdbWriteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} does not fit into {} columns",
+            self.field, self.value, self.width
+        )
+    }
+}
+
+impl Error for PdbWriteError {}
@@ -0,0 +1,105 @@
+/*!
+Contains parsers related to [Helix](http://www.wwpdb.org/documentation/file-format-content/format33/sect5.html#HELIX) records.
+HELIX records are used to identify the position of helices in the molecule. Helices are named, numbered and
+classified by type.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{character::complete::line_ending, do_parse, map, map_res, named, take, take_str};
+
+use std::str;
+
+/// columns 41 - 76 are frequently omitted, so comment and length are
+/// read from the remaining part of the line if they exist
+fn comment_and_length(rest: &[u8]) -> (String, u32) {
+    let column = |from: usize, to: usize| {
+        rest.get(from.min(rest.len())..to.min(rest.len()))
+            .and_then(|c| str::from_utf8(c).ok())
+            .map(str::trim)
+            .unwrap_or_default()
+    };
+    (
+        column(0, 30).to_owned(),
+        column(31, 36).parse().unwrap_or_default(),
+    )
+}
+
+named!(
+    #[doc=r#"Parses HELIX record. If successfull returns [Record](../ast/types/enum.Record.html) variant
+containing [Helix](../ast/types/struct.Helix.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD       | DEFINITION                                         |
+|----------|--------------|-------------|----------------------------------------------------|
+| 1 -  6   | Record name  | "HELIX "    |                                                    |
+| 8 - 10   | Integer      | serNum      | Serial number of the helix. This starts            |
+|          |              |             | at 1  and increases incrementally.                 |
+| 12 - 14  | LString(3)   | helixID     | Helix  identifier.                                 |
+| 16 - 18  | Residue name | initResName | Name of the initial residue.                       |
+| 20       | Character    | initChainID | Chain identifier for the chain containing          |
+|          |              |             | this  helix.                                       |
+| 22 - 25  | Integer      | initSeqNum  | Sequence number of the initial residue.            |
+| 26       | AChar        | initICode   | Insertion code of the initial residue.             |
+| 28 - 30  | Residue  name| endResName  | Name of the terminal residue of the helix.         |
+| 32       | Character    | endChainID  | Chain identifier for the chain containing          |
+|          |              |             | this  helix.                                       |
+| 34 - 37  | Integer      | endSeqNum   | Sequence number of the terminal residue.           |
+| 38       | AChar        | endICode    | Insertion code of the terminal residue.            |
+| 39 - 40  | Integer      | helixClass  | Helix class (see [HelixClass](../ast/types/enum.HelixClass.html)). |
+| 41 - 70  | String       | comment     | Comment about this helix.                          |
+| 72 - 76  | Integer      | length      | Length of this helix.                              |
+"#],
+    pub helix_record_parser<Record>,
+    do_parse!(
+        helix
+            >> take!(2)
+            >> serial: threedigit_integer
+            >> take!(1)
+            >> helix_id: map!(take_str!(3), str::trim)
+            >> take!(1)
+            >> start: spaced_residue_reference
+            >> take!(1)
+            >> end: spaced_residue_reference
+            >> class: map_res!(take_str!(2), str::parse::<HelixClass>)
+            >> rest: till_line_ending
+            >> line_ending
+            >> ({
+                let (comment, length) = comment_and_length(rest);
+                Record::Helix(Helix {
+                    serial,
+                    helix_id: helix_id.to_owned(),
+                    start,
+                    end,
+                    class,
+                    comment,
+                    length,
+                })
+            })
+    )
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn helix() {
+        if let Ok((_, Record::Helix(res))) = helix_record_parser(
+            "HELIX    1   1 ALA A   12  ASP A   14  5                                   3    \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.serial, 1);
+            assert_eq!(res.helix_id, "1");
+            assert_eq!(res.start.residue_name, "ALA");
+            assert_eq!(res.start.chain_id, 'A');
+            assert_eq!(res.start.sequence_number, 12);
+            assert_eq!(res.end.residue_name, "ASP");
+            assert_eq!(res.end.sequence_number, 14);
+            assert_eq!(res.class, HelixClass::RightHanded310);
+            assert_eq!(res.comment, "");
+            assert_eq!(res.length, 3);
+        } else {
+            panic!()
+        }
+    }
+}
@@ -0,0 +1,307 @@
+/*!
+Contains parsers related to the heterogen section records [Het](http://www.wwpdb.org/documentation/file-format-content/format33/sect4.html#HET),
+[Hetnam](http://www.wwpdb.org/documentation/file-format-content/format33/sect4.html#HETNAM),
+[Hetsyn](http://www.wwpdb.org/documentation/file-format-content/format33/sect4.html#HETSYN) and
+[Formul](http://www.wwpdb.org/documentation/file-format-content/format33/sect4.html#FORMUL).
+
+HET records describe every instance of a non-standard group, HETNAM and HETSYN records give the chemical name
+and synonyms of each group and FORMUL records give their chemical formulas.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{
+    character::complete::{anychar, line_ending},
+    do_parse, fold_many1, map, named, opt, tag, take, take_str,
+};
+
+use crate::make_line_folder;
+use std::{marker::PhantomData, str};
+
+#[allow(dead_code)]
+struct HetnamLine;
+
+#[allow(dead_code)]
+struct HetsynLine;
+
+#[allow(dead_code)]
+struct FormulLine;
+
+fn text(rest: &[u8]) -> String {
+    String::from_utf8_lossy(rest).trim_end().to_owned()
+}
+
+named!(
+    #[doc=r#"Parses a single HET record. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing [Het](../ast/types/struct.Het.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD       | DEFINITION                                         |
+|----------|--------------|-------------|----------------------------------------------------|
+| 1 -  6   | Record name  | "HET   "    |                                                    |
+| 8 - 10   | LString(3)   | hetID       | Het identifier, right-justified.                   |
+| 13       | Character    | ChainID     | Chain  identifier.                                 |
+| 14 - 17  | Integer      | seqNum      | Sequence  number.                                  |
+| 18       | AChar        | iCode       | Insertion  code.                                   |
+| 21 - 25  | Integer      | numHetAtoms | Number of HETATM records for the group present     |
+|          |              |             | in the entry.                                      |
+| 31 - 70  | String       | text        | Text describing Het group.                         |
+"#],
+    pub het_record_parser<Record>,
+    do_parse!(
+        het >> tag!("    ")
+            >> het_id: map!(take_str!(3), str::trim)
+            >> take!(2)
+            >> chain_id: anychar
+            >> sequence_number: fourdigit_hybrid36
+            >> insertion_code: optional_char
+            >> take!(2)
+            >> num_het_atoms: fivedigit_integer
+            >> rest: till_line_ending
+            >> line_ending
+            >> (Record::Het(Het {
+                het_id: het_id.to_owned(),
+                chain_id,
+                sequence_number,
+                insertion_code,
+                num_het_atoms,
+                text: text(rest.get(5..).unwrap_or_default()).trim_start().to_owned(),
+            }))
+    )
+);
+
+named!(
+    hetnam_line_parser<Continuation<HetnamLine>>,
+    do_parse!(
+        hetnam
+            >> take!(2)
+            >> continuation: twodigit_integer
+            >> take!(5)
+            >> remaining: till_line_ending
+            >> line_ending
+            >> (Continuation::<HetnamLine> {
+                continuation,
+                remaining: text(remaining).trim_start().to_owned(),
+                phantom: PhantomData,
+            })
+    )
+);
+
+make_line_folder!(hetnam_line_folder, hetnam_line_parser, HetnamLine);
+
+named!(
+    #[doc=r#"Parses HETNAM record of a single het group together with its continuation lines.
+Continuation lines are joined with a single space.
+If successfull returns [Record](../ast/types/enum.Record.html) variant containing [Hetnam](../ast/types/struct.Hetnam.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD        | DEFINITION                                         |
+|----------|--------------|--------------|----------------------------------------------------|
+| 1 -  6   | Record name  | "HETNAM"     |                                                    |
+| 9 - 10   | Continuation | continuation | Allows concatenation of multiple records.          |
+| 12 - 14  | LString(3)   | hetID        | Het identifier, right-justified.                   |
+| 16 - 70  | String       | text         | Chemical name.                                     |
+"#],
+    pub hetnam_record_parser<Record>,
+    do_parse!(
+        hetnam
+            >> tag!("     ")
+            >> het_id: map!(take_str!(3), str::trim)
+            >> take!(1)
+            >> first: till_line_ending
+            >> line_ending
+            >> rest: opt!(hetnam_line_folder)
+            >> (Record::Hetnam(Hetnam {
+                het_id: het_id.to_owned(),
+                name: join(text(first), rest),
+            }))
+    )
+);
+
+named!(
+    hetsyn_line_parser<Continuation<HetsynLine>>,
+    do_parse!(
+        hetsyn
+            >> take!(2)
+            >> continuation: twodigit_integer
+            >> take!(5)
+            >> remaining: till_line_ending
+            >> line_ending
+            >> (Continuation::<HetsynLine> {
+                continuation,
+                remaining: text(remaining).trim_start().to_owned(),
+                phantom: PhantomData,
+            })
+    )
+);
+
+make_line_folder!(hetsyn_line_folder, hetsyn_line_parser, HetsynLine);
+
+named!(
+    #[doc=r#"Parses HETSYN record of a single het group together with its continuation lines.
+Synonyms are separated by semicolons.
+If successfull returns [Record](../ast/types/enum.Record.html) variant containing [Hetsyn](../ast/types/struct.Hetsyn.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD           | DEFINITION                                      |
+|----------|--------------|-----------------|-------------------------------------------------|
+| 1 -  6   | Record name  | "HETSYN"        |                                                 |
+| 9 - 10   | Continuation | continuation    | Allows concatenation of multiple records.       |
+| 12 - 14  | LString(3)   | hetID           | Het identifier, right-justified.                |
+| 16 - 70  | SList        | hetSynonyms     | List of synonyms.                               |
+"#],
+    pub hetsyn_record_parser<Record>,
+    do_parse!(
+        hetsyn
+            >> tag!("     ")
+            >> het_id: map!(take_str!(3), str::trim)
+            >> take!(1)
+            >> first: till_line_ending
+            >> line_ending
+            >> rest: opt!(hetsyn_line_folder)
+            >> (Record::Hetsyn(Hetsyn {
+                het_id: het_id.to_owned(),
+                synonyms: join(text(first), rest)
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_owned)
+                    .collect(),
+            }))
+    )
+);
+
+named!(
+    formul_line_parser<Continuation<FormulLine>>,
+    do_parse!(
+        formul
+            >> take!(10)
+            >> continuation: twodigit_integer
+            >> take!(1)
+            >> remaining: till_line_ending
+            >> line_ending
+            >> (Continuation::<FormulLine> {
+                continuation,
+                remaining: text(remaining).trim_start().to_owned(),
+                phantom: PhantomData,
+            })
+    )
+);
+
+make_line_folder!(formul_line_folder, formul_line_parser, FormulLine);
+
+named!(
+    #[doc=r#"Parses FORMUL record of a single het group together with its continuation lines.
+If successfull returns [Record](../ast/types/enum.Record.html) variant containing [Formul](../ast/types/struct.Formul.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD        | DEFINITION                                         |
+|----------|--------------|--------------|----------------------------------------------------|
+| 1 -  6   | Record name  | "FORMUL"     |                                                    |
+| 9 - 10   | Integer      | compNum      | Component  number.                                 |
+| 13 - 15  | LString(3)   | hetID        | Het identifier.                                    |
+| 17 - 18  | Integer      | continuation | Continuation number.                               |
+| 19       | Character    | asterisk     | "*" for water.                                     |
+| 20 - 70  | String       | text         | Chemical formula.                                  |
+"#],
+    pub formul_record_parser<Record>,
+    do_parse!(
+        formul
+            >> take!(2)
+            >> component_number: twodigit_integer
+            >> take!(2)
+            >> het_id: map!(take_str!(3), str::trim)
+            >> tag!("   ")
+            >> asterisk: anychar
+            >> first: till_line_ending
+            >> line_ending
+            >> rest: opt!(formul_line_folder)
+            >> (Record::Formul(Formul {
+                component_number,
+                het_id: het_id.to_owned(),
+                water: asterisk == '*',
+                formula: join(text(first), rest),
+            }))
+    )
+);
+
+/// joins the first line of a record with its continuation lines
+fn join(first: String, rest: Option<Vec<u8>>) -> String {
+    match rest {
+        Some(rest) => first + " " + &String::from_utf8_lossy(&rest),
+        None => first,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn het() {
+        if let Ok((_, Record::Het(res))) = het_record_parser(
+            "HET    MAN  A 328      22                                                       \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.het_id, "MAN");
+            assert_eq!(res.chain_id, 'A');
+            assert_eq!(res.sequence_number, 328);
+            assert_eq!(res.insertion_code, None);
+            assert_eq!(res.num_het_atoms, 22);
+            assert_eq!(res.text, "");
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn hetnam() {
+        if let Ok((rest, Record::Hetnam(res))) = hetnam_record_parser(
+            r#"HETNAM     PP7 METHYL CYCLO[(2S)-2-[[(1R)-1-(N-(L-N-(3-                         
+HETNAM   2 PP7  METHYLBUTANOYL)VALYL-L-ASPARTYL)AMINO)-3-                       
+HETNAM   3 PP7  PHENYLPROPANOATE                                                
+HETNAM     GOL GLYCEROL                                                         
+"#
+            .as_bytes(),
+        ) {
+            assert_eq!(res.het_id, "PP7");
+            assert_eq!(
+                res.name,
+                "METHYL CYCLO[(2S)-2-[[(1R)-1-(N-(L-N-(3- METHYLBUTANOYL)VALYL-L-ASPARTYL)AMINO)-3- PHENYLPROPANOATE"
+            );
+            assert!(rest.starts_with(b"HETNAM     GOL"));
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn hetsyn() {
+        if let Ok((_, Record::Hetsyn(res))) = hetsyn_record_parser(
+            "HETSYN     GOL GLYCERIN; PROPANE-1,2,3-TRIOL                                    \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.het_id, "GOL");
+            assert_eq!(res.synonyms, vec!["GLYCERIN", "PROPANE-1,2,3-TRIOL"]);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn formul() {
+        if let Ok((_, Record::Formul(res))) = formul_record_parser(
+            "FORMUL   8  HOH   *528(H2 O)                                                    \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.component_number, 8);
+            assert_eq!(res.het_id, "HOH");
+            assert!(res.water);
+            assert_eq!(res.formula, "528(H2 O)");
+        } else {
+            panic!()
+        }
+    }
+}
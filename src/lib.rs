@@ -6,17 +6,29 @@ traversable record struct.
 
 extern crate nom;
 
+pub mod anisou;
 mod ast;
+pub mod atom;
 pub mod author;
 pub mod caveat;
+pub mod cif;
+pub mod cispep;
 pub mod compnd;
+pub mod conect;
+pub mod cryst1;
 pub mod dbref;
 pub mod dbref1;
+pub mod error;
 pub mod expdta;
 pub mod header;
+pub mod helix;
+pub mod het;
 pub mod jrnl;
 pub mod keywds;
+pub mod link;
+pub mod master;
 pub mod mdltyp;
+pub mod model;
 pub mod modres;
 pub mod nummdl;
 pub mod obslte;
@@ -26,11 +38,20 @@ pub mod remark;
 pub mod revdat;
 pub mod seqadv;
 pub mod seqres;
+pub mod sheet;
+pub mod site;
 pub mod source;
 pub mod split;
 pub mod sprsde;
+pub mod ssbond;
+pub mod symmetry;
+pub mod ter;
 pub mod title;
+pub mod transformation;
+pub mod writer;
 
-pub use ast::{pdb_file::*, types::*};
+pub use ast::{pdb_file::*, structure, types::*};
+pub use cif::parse_cif;
+pub use error::{MasterCountError, MasterField, PdbParseError, PdbWriteError, SeqresLengthError};
 pub use nom::IResult;
-pub use record::parse;
+pub use record::{parse, parse_lenient, parse_lossless, parse_strict};
@@ -0,0 +1,116 @@
+/*!
+Contains parsers related to [Link](http://www.wwpdb.org/documentation/file-format-content/format33/sect6.html#LINK) records.
+The LINK records specify connectivity between residues that is not implied by the primary structure, such as
+covalent links of ligands or metal coordination.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{character::complete::line_ending, do_parse, map_res, named, tag, take, value};
+
+named!(
+    #[doc=r#"Parses LINK record. If successfull returns [Record](../ast/types/enum.Record.html) variant
+containing [Link](../ast/types/struct.Link.html) instance. Symmetry operators and link distance are
+`None` when their columns are blank, a value that does not parse fails the record.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD       | DEFINITION                                         |
+|----------|--------------|-------------|----------------------------------------------------|
+| 1 -  6   | Record name  | "LINK  "    |                                                    |
+| 13 - 16  | Atom         | name1       | Atom name.                                         |
+| 17       | Character    | altLoc1     | Alternate location indicator.                      |
+| 18 - 20  | Residue name | resName1    | Residue  name.                                     |
+| 22       | Character    | chainID1    | Chain identifier.                                  |
+| 23 - 26  | Integer      | resSeq1     | Residue sequence number.                           |
+| 27       | AChar        | iCode1      | Insertion code.                                    |
+| 43 - 46  | Atom         | name2       | Atom name.                                         |
+| 47       | Character    | altLoc2     | Alternate location indicator.                      |
+| 48 - 50  | Residue name | resName2    | Residue name.                                      |
+| 52       | Character    | chainID2    | Chain identifier.                                  |
+| 53 - 56  | Integer      | resSeq2     | Residue sequence number.                           |
+| 57       | AChar        | iCode2      | Insertion code.                                    |
+| 60 - 65  | SymOP        | sym1        | Symmetry operator atom 1.                          |
+| 67 - 72  | SymOP        | sym2        | Symmetry operator atom 2.                          |
+| 74 – 78  | Real(5.2)    | Length      | Link distance                                      |
+"#],
+    pub link_record_parser<Record>,
+    do_parse!(
+        link >> tag!("  ")
+            >> take!(6)
+            >> atom1: located_atom_reference
+            >> take!(15)
+            >> atom2: located_atom_reference
+            >> rest: till_line_ending
+            >> symmetry1: map_res!(value!(rest), |r| parsed_column(r, 2, 8))
+            >> symmetry2: map_res!(value!(rest), |r| parsed_column(r, 9, 15))
+            >> length: map_res!(value!(rest), |r| parsed_column(r, 16, 21))
+            >> line_ending
+            >> (Record::Link(Link {
+                atom1,
+                atom2,
+                symmetry1,
+                symmetry2,
+                length,
+            }))
+    )
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn link() {
+        if let Ok((_, Record::Link(res))) = link_record_parser(
+            "LINK         C1  MAN A 328                 OG  SER A   3     1555   1555  1.42  \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.atom1.name, "C1");
+            assert_eq!(res.atom1.alt_loc, None);
+            assert_eq!(res.atom1.residue_name, "MAN");
+            assert_eq!(res.atom1.sequence_number, 328);
+            assert_eq!(res.atom2.name, "OG");
+            assert_eq!(res.atom2.residue_name, "SER");
+            assert_eq!(res.atom2.chain_id, 'A');
+            assert_eq!(res.atom2.sequence_number, 3);
+            assert_eq!(res.symmetry2.unwrap().operator, 1);
+            assert_eq!(res.length, Some(1.42));
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn link_alternate_location() {
+        if let Ok((_, Record::Link(res))) = link_record_parser(
+            "LINK        ZN    ZN A 301                 NE2BHIS A  96     1555   6555  2.05\n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.atom1.name, "ZN");
+            assert_eq!(res.atom2.alt_loc, Some('B'));
+            assert_eq!(
+                res.symmetry2,
+                Some(SymmetryOperator {
+                    operator: 6,
+                    translation: [0, 0, 0]
+                })
+            );
+            assert_eq!(res.length, Some(2.05));
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn link_malformed_columns() {
+        assert!(link_record_parser(
+            "LINK         C1  MAN A 328                 OG  SER A   3     1555   15x5  1.42  \n"
+                .as_bytes(),
+        )
+        .is_err());
+        assert!(link_record_parser(
+            "LINK         C1  MAN A 328                 OG  SER A   3     1555   1555  1.4?  \n"
+                .as_bytes(),
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,152 @@
+/*!
+Contains parsers related to the bookkeeping records [Master](http://www.wwpdb.org/documentation/file-format-content/format33/sect11.html#MASTER)
+and [End](http://www.wwpdb.org/documentation/file-format-content/format33/sect11.html#END).
+The MASTER record is a control record for bookkeeping. It lists the number of lines in the coordinate
+entry or file for selected record types. The END record marks the end of the entry.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{character::complete::line_ending, do_parse, named, take};
+
+named!(
+    #[doc=r#"Parses a single MASTER record. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing [Master](../ast/types/struct.Master.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD      | DEFINITION                                          |
+|----------|--------------|------------|-----------------------------------------------------|
+| 1 -  6   | Record name  | "MASTER"   |                                                     |
+| 11 - 15  | Integer      | numRemark  | Number of REMARK records                            |
+| 16 - 20  | Integer      | "0"        |                                                     |
+| 21 - 25  | Integer      | numHet     | Number of HET records                               |
+| 26 - 30  | Integer      | numHelix   | Number of HELIX records                             |
+| 31 - 35  | Integer      | numSheet   | Number of SHEET records                             |
+| 36 - 40  | Integer      | numTurn    | deprecated                                          |
+| 41 - 45  | Integer      | numSite    | Number of SITE records                              |
+| 46 - 50  | Integer      | numXform   | Number of coordinate transformation                 |
+|          |              |            | records  (ORIGX+SCALE+MTRIX)                        |
+| 51 - 55  | Integer      | numCoord   | Number of atomic coordinate records                 |
+|          |              |            | records (ATOM+HETATM)                               |
+| 56 - 60  | Integer      | numTer     | Number of TER records                               |
+| 61 - 65  | Integer      | numConect  | Number of CONECT records                            |
+| 66 - 70  | Integer      | numSeq     | Number of SEQRES records                            |
+"#],
+    pub master_record_parser<Record>,
+    do_parse!(
+        master
+            >> take!(4)
+            >> num_remark: fivedigit_integer
+            >> take!(5)
+            >> num_het: fivedigit_integer
+            >> num_helix: fivedigit_integer
+            >> num_sheet: fivedigit_integer
+            >> num_turn: fivedigit_integer
+            >> num_site: fivedigit_integer
+            >> num_xform: fivedigit_integer
+            >> num_coord: fivedigit_integer
+            >> num_ter: fivedigit_integer
+            >> num_conect: fivedigit_integer
+            >> num_seq: fivedigit_integer
+            >> till_line_ending
+            >> line_ending
+            >> (Record::Master(Master {
+                num_remark,
+                num_het,
+                num_helix,
+                num_sheet,
+                num_turn,
+                num_site,
+                num_xform,
+                num_coord,
+                num_ter,
+                num_conect,
+                num_seq,
+            }))
+    )
+);
+
+named!(
+    #[doc=r#"Parses the END record which marks the end of the entry.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD    | DEFINITION |
+|----------|--------------|----------|------------|
+| 1 -  6   | Record name  | "END   " |            |
+"#],
+    pub end_record_parser<Record>,
+    do_parse!(end >> till_line_ending >> line_ending >> (Record::End))
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        error::{MasterCountError, MasterField},
+        record::test::{get_test_file_path, read_file},
+    };
+
+    #[test]
+    fn master() {
+        if let Ok((_, Record::Master(res))) = master_record_parser(
+            "MASTER      478    0    6   10   29    0   20    6 2977    1  139   25          \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.num_remark, 478);
+            assert_eq!(res.num_het, 6);
+            assert_eq!(res.num_helix, 10);
+            assert_eq!(res.num_sheet, 29);
+            assert_eq!(res.num_turn, 0);
+            assert_eq!(res.num_site, 20);
+            assert_eq!(res.num_xform, 6);
+            assert_eq!(res.num_coord, 2977);
+            assert_eq!(res.num_ter, 1);
+            assert_eq!(res.num_conect, 139);
+            assert_eq!(res.num_seq, 25);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn end() {
+        assert!(end_record_parser(
+            "END                                                                             \n"
+                .as_bytes()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn bookkeeping() {
+        let contents = read_file(&get_test_file_path("1NLS.pdb"));
+        let (_, pdb) = crate::parse(&contents).unwrap();
+        assert_eq!(pdb.coordinates().terminations().count(), 1);
+        assert_eq!(pdb.connectivity().conects().count(), 14);
+        assert_eq!(pdb.connectivity().bonded_to(74), vec![1874, 1875]);
+        assert_eq!(
+            pdb.connectivity().bonded_to(1874),
+            vec![56, 74, 142, 187, 2047, 2048]
+        );
+        assert_eq!(pdb.master().unwrap().num_conect, 16);
+        assert_eq!(pdb.validate_master(), Ok(()));
+
+        let contents = read_file(&get_test_file_path("1BYI.pdb"));
+        let (_, pdb) = crate::parse(&contents).unwrap();
+        assert_eq!(
+            pdb.validate_master(),
+            Err(vec![MasterCountError {
+                field: MasterField::Coord,
+                master: 2095,
+                counted: 2285,
+            }])
+        );
+
+        let lines = r#"CONECT 1179  746 1184
+CONECT 1179 1195 1203
+CONECT 1179 1211
+"#;
+        let (_, pdb) = crate::parse(lines).unwrap();
+        assert_eq!(pdb.counted_master().num_conect, 3);
+    }
+}
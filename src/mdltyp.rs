@@ -26,7 +26,7 @@ named!(
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<MdltypLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
+                continuation: cont.unwrap_or_default(),
                 remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
                 phantom: PhantomData,
             })
@@ -0,0 +1,73 @@
+/*!
+Contains parsers related to [Model](http://www.wwpdb.org/documentation/file-format-content/format33/sect9.html#MODEL)
+and [Endmdl](http://www.wwpdb.org/documentation/file-format-content/format33/sect9.html#ENDMDL) records.
+The MODEL record specifies the model serial number when multiple models of the same structure are presented
+in a single coordinate entry, as is often the case with structures determined by NMR. ENDMDL records are paired
+with MODEL records to group individual structures found in a coordinate entry.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{
+    character::complete::{line_ending, space1},
+    do_parse, named,
+};
+
+named!(
+    #[doc=r#"Parses a single MODEL record. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing [Model](../ast/types/struct.Model.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD   | DEFINITION            |
+|----------|--------------|---------|-----------------------|
+| 1 -  6   | Record name  | "MODEL "|                       |
+| 11 - 14  | Integer      | serial  | Model serial number.  |
+"#],
+    pub model_record_parser<Record>,
+    do_parse!(
+        model
+            >> space1
+            >> serial: integer
+            >> till_line_ending
+            >> line_ending
+            >> (Record::Model(Model { serial }))
+    )
+);
+
+named!(
+    #[doc=r#"Parses a single ENDMDL record which closes the preceding MODEL record.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD    | DEFINITION |
+|----------|--------------|----------|------------|
+| 1 -  6   | Record name  | "ENDMDL" |            |
+"#],
+    pub endmdl_record_parser<Record>,
+    do_parse!(endmdl >> till_line_ending >> line_ending >> (Record::Endmdl))
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn model() {
+        if let Ok((_, Record::Model(res))) = model_record_parser(
+            "MODEL       12                                                                  \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.serial, 12);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn endmdl() {
+        assert!(endmdl_record_parser(
+            "ENDMDL                                                                          \n"
+                .as_bytes()
+        )
+        .is_ok());
+    }
+}
@@ -1,7 +1,7 @@
 use super::{ast::types::*, primitive::*};
 use nom::{
     character::complete::{anychar, line_ending, space1},
-    do_parse, named, opt,
+    do_parse, named, take,
 };
 
 named!(
@@ -14,9 +14,9 @@ named!(
            >> residue_name : residue_parser
            >> space1
            >> chain_id : anychar
-           >> space1
-           >> sequence_number : integer
-           >> insertion_code : opt!(anychar) // TODO this should be none empty char.
+           >> take!(1)
+           >> sequence_number : fourdigit_hybrid36
+           >> insertion_code : optional_char
            >> space1
            >> standart_residue_name : residue_parser
            >> space1
@@ -52,7 +52,7 @@ pub mod test {
                 assert_eq!(res.residue_name, "ASN");
                 assert_eq!(res.chain_id, 'A');
                 assert_eq!(res.sequence_number, 74);
-                assert_eq!(res.insertion_code, Some(' '));
+                assert_eq!(res.insertion_code, None);
                 assert_eq!(res.standart_residue_name, "ASN");
             }
             Ok((_, _)) => {
@@ -15,13 +15,13 @@ use nom::{
         is_alphanumeric, is_digit, is_space,
     },
     combinator::{map, map_res},
-    do_parse, fold_many0, map_res,
+    do_parse, fold_many0, map, map_opt, map_res,
     multi::separated_list,
     named, separated_list,
     sequence::tuple,
     tag, take, take_str, IResult,
 };
-use std::{result::Result, str, str::FromStr};
+use std::{convert::TryFrom, result::Result, str, str::FromStr};
 
 macro_rules! make_tagger(
     ($fnname:ident) =>(
@@ -78,6 +78,18 @@ macro_rules! make_line_folder (
                 )
             );
     };
+    ($parser_name : ident, $line_parser : ident, $line_type : ty, fixed_columns) => {
+        named!(
+            $parser_name<Vec<u8>>,
+            fold_many1!(
+                    $line_parser,
+                    Vec::new(),
+                    |acc : Vec<u8>, item : Continuation<$line_type>|{
+                        acc.into_iter().chain(item.remaining.bytes()).collect()
+                    }
+                )
+            );
+    };
 );
 
 make_tagger!(master);
@@ -107,6 +119,27 @@ make_tagger!(dbref2);
 make_tagger!(seqadv);
 make_tagger!(modres);
 make_tagger!(remark);
+make_tagger!(atom);
+make_tagger!(hetatm);
+make_tagger!(anisou);
+make_tagger!(ter);
+make_tagger!(conect);
+make_tagger!(model);
+make_tagger!(endmdl);
+make_tagger!(het);
+make_tagger!(hetnam);
+make_tagger!(hetsyn);
+make_tagger!(formul);
+make_tagger!(helix);
+make_tagger!(sheet);
+make_tagger!(ssbond);
+make_tagger!(link);
+make_tagger!(cispep);
+make_tagger!(site);
+make_tagger!(cryst1);
+make_tagger!(origx);
+make_tagger!(scale);
+make_tagger!(mtrix);
 
 named!(
     #[doc=r#"
@@ -158,6 +191,221 @@ named!(
     map_res!(map_res!(take!(5), str::from_utf8), |s : &str| str::FromStr::from_str(s.trim()))
 );
 
+/// Decodes a [hybrid-36](http://cci.lbl.gov/hybrid_36/) number of a `width` column field.
+/// Values up to `10^width - 1` are written as decimal numbers, larger ones continue with
+/// upper case base 36 numbers starting at `A000..` and then lower case ones starting at
+/// `a000..`. Decimal numbers may be surrounded with spaces. Returns `None` for a `width`
+/// outside `1..=12`.
+/// # Example
+/// ```
+/// # use patoz::primitive::hybrid36_decode;
+/// assert_eq!(hybrid36_decode(5, "99999"), Some(99999));
+/// assert_eq!(hybrid36_decode(5, "A0000"), Some(100000));
+/// assert_eq!(hybrid36_decode(4, "a000"), Some(10000 + 26 * 36 * 36 * 36));
+/// assert_eq!(hybrid36_decode(4, "  -3"), Some(-3));
+/// assert_eq!(hybrid36_decode(4, "A0"), None);
+/// assert_eq!(hybrid36_decode(0, "1"), None);
+/// ```
+pub fn hybrid36_decode(width: usize, s: &str) -> Option<i64> {
+    if !(1..=12).contains(&width) {
+        return None;
+    }
+    let first = s.chars().next()?;
+    if !first.is_ascii_alphabetic() {
+        return s.trim().parse().ok();
+    }
+    if s.len() != width {
+        return None;
+    }
+    let upper = first.is_ascii_uppercase();
+    let value = s.bytes().try_fold(0i64, |value, c| {
+        let digit = match c {
+            b'0'..=b'9' => c - b'0',
+            b'A'..=b'Z' if upper => c - b'A' + 10,
+            b'a'..=b'z' if !upper => c - b'a' + 10,
+            _ => return None,
+        };
+        Some(value * 36 + i64::from(digit))
+    })?;
+    let decimal_limit = 10i64.pow(width as u32);
+    let block = 36i64.pow(width as u32 - 1);
+    if upper {
+        Some(value - 10 * block + decimal_limit)
+    } else {
+        Some(value + 16 * block + decimal_limit)
+    }
+}
+
+/// Encodes a number as [hybrid-36](http://cci.lbl.gov/hybrid_36/) for a `width` column field.
+/// Returns `None` if the value does not fit or `width` is outside `1..=12`. The result is
+/// not padded to `width`.
+/// # Example
+/// ```
+/// # use patoz::primitive::hybrid36_encode;
+/// assert_eq!(hybrid36_encode(5, 12).as_deref(), Some("12"));
+/// assert_eq!(hybrid36_encode(5, 100000).as_deref(), Some("A0000"));
+/// assert_eq!(hybrid36_encode(4, 10000 + 26 * 36 * 36 * 36).as_deref(), Some("a000"));
+/// assert_eq!(hybrid36_encode(4, -1000), None);
+/// assert_eq!(hybrid36_encode(0, 0), None);
+/// assert_eq!(hybrid36_encode(13, 0), None);
+/// ```
+pub fn hybrid36_encode(width: usize, value: i64) -> Option<String> {
+    if !(1..=12).contains(&width) {
+        return None;
+    }
+    let decimal_limit = 10i64.pow(width as u32);
+    let block = 36i64.pow(width as u32 - 1);
+    if value > -(decimal_limit / 10) && value < decimal_limit {
+        return Some(value.to_string());
+    }
+    let (mut remaining, digits): (i64, &[u8]) = match value - decimal_limit {
+        v if (0..26 * block).contains(&v) => {
+            (v + 10 * block, b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ")
+        }
+        v if (26 * block..52 * block).contains(&v) => {
+            (v - 16 * block, b"0123456789abcdefghijklmnopqrstuvwxyz")
+        }
+        _ => return None,
+    };
+    let mut encoded = vec![0u8; width];
+    for c in encoded.iter_mut().rev() {
+        *c = digits[(remaining % 36) as usize];
+        remaining /= 36;
+    }
+    String::from_utf8(encoded).ok()
+}
+
+named!(
+    #[doc=r#"
+Parses five column wide [hybrid-36](fn.hybrid36_decode.html) numbers such as atom serial numbers.
+# Example
+```
+# use patoz::primitive::fivedigit_hybrid36;
+let empty_remaining  : [u8;0] = [];
+assert_eq!(Ok((&empty_remaining[..],1849)), fivedigit_hybrid36(" 1849".as_bytes()));
+assert_eq!(Ok((&empty_remaining[..],100000)), fivedigit_hybrid36("A0000".as_bytes()));
+```
+    "#],
+    pub fivedigit_hybrid36<u32>,
+    map_opt!(take_str!(5), |s: &str| hybrid36_decode(5, s).and_then(|v| u32::try_from(v).ok()))
+);
+
+named!(
+    #[doc=r#"
+Parses four column wide [hybrid-36](fn.hybrid36_decode.html) numbers such as residue sequence
+numbers. Decimal numbers may carry a leading minus sign.
+# Example
+```
+# use patoz::primitive::fourdigit_hybrid36;
+let empty_remaining  : [u8;0] = [];
+assert_eq!(Ok((&empty_remaining[..],-3)), fourdigit_hybrid36("  -3".as_bytes()));
+assert_eq!(Ok((&empty_remaining[..],10000)), fourdigit_hybrid36("A000".as_bytes()));
+```
+    "#],
+    pub fourdigit_hybrid36<i32>,
+    map_opt!(take_str!(4), |s: &str| hybrid36_decode(4, s).and_then(|v| i32::try_from(v).ok()))
+);
+
+named!(
+    #[doc=r#"
+Parses seven column wide integers which may carry a leading minus sign, such as
+anisotropic temperature factors.
+# Example
+```
+# use patoz::primitive::sevendigit_signed_integer;
+let empty_remaining  : [u8;0] = [];
+assert_eq!(Ok((&empty_remaining[..],-123)), sevendigit_signed_integer("   -123".as_bytes()));
+```
+    "#],
+    pub sevendigit_signed_integer<i32>,
+    map_res!(map_res!(take!(7), str::from_utf8), |s : &str| str::FromStr::from_str(s.trim()))
+);
+
+named!(
+    #[doc=r#"
+Parses six column wide real numbers such as occupancy and temperature factor.
+# Example
+```
+# use patoz::primitive::sixdigit_real;
+let empty_remaining  : [u8;0] = [];
+assert_eq!(Ok((&empty_remaining[..],20.91)), sixdigit_real(" 20.91".as_bytes()));
+```
+    "#],
+    pub sixdigit_real<f64>,
+    map_res!(map_res!(take!(6), str::from_utf8), |s : &str| str::FromStr::from_str(s.trim()))
+);
+
+named!(
+    #[doc=r#"
+Parses eight column wide real numbers such as orthogonal coordinates.
+# Example
+```
+# use patoz::primitive::eightdigit_real;
+let empty_remaining  : [u8;0] = [];
+assert_eq!(Ok((&empty_remaining[..],-16.25)), eightdigit_real(" -16.250".as_bytes()));
+```
+    "#],
+    pub eightdigit_real<f64>,
+    map_res!(map_res!(take!(8), str::from_utf8), |s : &str| str::FromStr::from_str(s.trim()))
+);
+
+named!(
+    #[doc=r#"
+Parses seven column wide real numbers such as unit cell angles.
+# Example
+```
+# use patoz::primitive::sevendigit_real;
+let empty_remaining  : [u8;0] = [];
+assert_eq!(Ok((&empty_remaining[..],113.55)), sevendigit_real(" 113.55".as_bytes()));
+```
+    "#],
+    pub sevendigit_real<f64>,
+    map_res!(map_res!(take!(7), str::from_utf8), |s : &str| str::FromStr::from_str(s.trim()))
+);
+
+named!(
+    #[doc=r#"
+Parses nine column wide real numbers such as unit cell lengths.
+# Example
+```
+# use patoz::primitive::ninedigit_real;
+let empty_remaining  : [u8;0] = [];
+assert_eq!(Ok((&empty_remaining[..],52.426)), ninedigit_real("   52.426".as_bytes()));
+```
+    "#],
+    pub ninedigit_real<f64>,
+    map_res!(map_res!(take!(9), str::from_utf8), |s : &str| str::FromStr::from_str(s.trim()))
+);
+
+named!(
+    #[doc=r#"
+Parses ten column wide real numbers such as transformation matrix elements.
+# Example
+```
+# use patoz::primitive::tendigit_real;
+let empty_remaining  : [u8;0] = [];
+assert_eq!(Ok((&empty_remaining[..],0.010311)), tendigit_real("  0.010311".as_bytes()));
+```
+    "#],
+    pub tendigit_real<f64>,
+    map_res!(map_res!(take!(10), str::from_utf8), |s : &str| str::FromStr::from_str(s.trim()))
+);
+
+named!(
+    #[doc=r#"
+Parses a single column character field. A blank column is returned as `None`.
+# Example
+```
+# use patoz::primitive::optional_char;
+let empty_remaining  : [u8;0] = [];
+assert_eq!(Ok((&empty_remaining[..],Some('A'))), optional_char("A".as_bytes()));
+assert_eq!(Ok((&empty_remaining[..],None)), optional_char(" ".as_bytes()));
+```
+    "#],
+    pub optional_char<Option<char>>,
+    map!(take!(1), |c: &[u8]| if c[0] == b' ' { None } else { Some(char::from(c[0])) })
+);
+
 named!(
     #[doc=r#"
 Parses arbitrary digit positive integers. Needs at least one digit.
@@ -278,13 +526,16 @@ named!(
 
 named!(
     pub date_parser<NaiveDate>,
-    do_parse!(
-        dayp: integer
-            >> tag!("-")
-            >> monthp: month_parser
-            >> tag!("-")
-            >> yearp: integer
-            >> (NaiveDate::from_ymd(yearp as i32, monthp, dayp))
+    map_opt!(
+        do_parse!(
+            dayp: integer
+                >> tag!("-")
+                >> monthp: month_parser
+                >> tag!("-")
+                >> yearp: integer
+                >> ((yearp, monthp, dayp))
+        ),
+        |(yearp, monthp, dayp): (u32, u32, u32)| NaiveDate::from_ymd_opt(yearp as i32, monthp, dayp)
     )
 );
 
@@ -407,7 +658,7 @@ wrap_len!(five_space, String, 5u32, mspace);
 
 use crate::{Dbref, Dbref2};
 
-use super::ast::types::ModificationType;
+use super::ast::types::{AtomReference, ModificationType, ResidueReference};
 
 named!(
     pub modification_type_parser<ModificationType>,
@@ -418,6 +669,116 @@ named!(
     )
 );
 
+named!(
+    #[doc=r#"
+Parses a residue reference whose chain identifier directly precedes the sequence number,
+as in SHEET and LINK records. Residue name, blank, chain identifier, sequence number and insertion code.
+# Example
+```
+# use patoz::primitive::residue_reference;
+let (_, res) = residue_reference("GLY A  45A".as_bytes()).unwrap();
+assert_eq!((res.residue_name.as_str(), res.chain_id, res.sequence_number, res.insertion_code), ("GLY", 'A', 45, Some('A')));
+```
+    "#],
+    pub residue_reference<ResidueReference>,
+    do_parse!(
+        residue_name: map!(take_str!(3), str::trim)
+            >> take!(1)
+            >> chain_id: map!(take!(1), |c: &[u8]| char::from(c[0]))
+            >> sequence_number: fourdigit_hybrid36
+            >> insertion_code: optional_char
+            >> (ResidueReference {
+                residue_name: residue_name.to_owned(),
+                chain_id,
+                sequence_number,
+                insertion_code,
+            })
+    )
+);
+
+named!(
+    #[doc=r#"
+Parses a residue reference having a blank column between the chain identifier and the sequence number,
+as in HELIX, SSBOND and CISPEP records.
+# Example
+```
+# use patoz::primitive::spaced_residue_reference;
+let (_, res) = spaced_residue_reference("ALA A   12 ".as_bytes()).unwrap();
+assert_eq!((res.residue_name.as_str(), res.chain_id, res.sequence_number, res.insertion_code), ("ALA", 'A', 12, None));
+```
+    "#],
+    pub spaced_residue_reference<ResidueReference>,
+    do_parse!(
+        residue_name: map!(take_str!(3), str::trim)
+            >> take!(1)
+            >> chain_id: map!(take!(1), |c: &[u8]| char::from(c[0]))
+            >> take!(1)
+            >> sequence_number: fourdigit_hybrid36
+            >> insertion_code: optional_char
+            >> (ResidueReference {
+                residue_name: residue_name.to_owned(),
+                chain_id,
+                sequence_number,
+                insertion_code,
+            })
+    )
+);
+
+named!(
+    #[doc=r#"
+Parses an atom reference. Four column atom name followed by a [residue reference](fn.residue_reference.html).
+    "#],
+    pub atom_reference<AtomReference>,
+    do_parse!(
+        name: map!(take_str!(4), str::trim)
+            >> residue: residue_reference
+            >> (AtomReference {
+                name: name.to_owned(),
+                alt_loc: None,
+                residue_name: residue.residue_name,
+                chain_id: residue.chain_id,
+                sequence_number: residue.sequence_number,
+                insertion_code: residue.insertion_code,
+            })
+    )
+);
+
+named!(
+    #[doc=r#"
+Parses an atom reference carrying an alternate location indicator as in LINK records.
+Four column atom name, alternate location and a [residue reference](fn.residue_reference.html).
+    "#],
+    pub located_atom_reference<AtomReference>,
+    do_parse!(
+        name: map!(take_str!(4), str::trim)
+            >> alt_loc: optional_char
+            >> residue: residue_reference
+            >> (AtomReference {
+                name: name.to_owned(),
+                alt_loc,
+                residue_name: residue.residue_name,
+                chain_id: residue.chain_id,
+                sequence_number: residue.sequence_number,
+                insertion_code: residue.insertion_code,
+            })
+    )
+);
+
+/// trimmed content of columns `from..to` of `rest`. Returns `None` if the
+/// columns are blank or lie beyond the end of a truncated line
+pub fn optional_column(rest: &[u8], from: usize, to: usize) -> Option<&str> {
+    rest.get(from.min(rest.len())..to.min(rest.len()))
+        .and_then(|c| str::from_utf8(c).ok())
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+}
+
+/// parses the trimmed content of columns `from..to` of `rest`. Returns `Ok(None)` if the
+/// columns are blank and an error if they hold a value that does not parse
+pub fn parsed_column<T: FromStr>(rest: &[u8], from: usize, to: usize) -> Result<Option<T>, T::Err> {
+    optional_column(rest, from, to).map(str::parse).transpose()
+}
+
 make_token_tagger!(mol_id);
 make_token_tagger!(molecule);
 make_token_tagger!(chain);
@@ -445,7 +806,7 @@ make_token_tagger!(plasmid);
 make_token_tagger!(gene);
 make_token_tagger!(expression_system);
 make_token_tagger!(expression_system_common);
-make_token_tagger!(expression_system_tax_id);
+make_token_tagger!(expression_system_taxid);
 make_token_tagger!(expression_system_strain);
 make_token_tagger!(expression_system_variant);
 make_token_tagger!(expression_system_cell_line);
@@ -485,22 +846,20 @@ mod test {
     #[test]
     fn test_yes_parser() {
         if let Ok((_, res)) = yes("YES".as_bytes()) {
-            assert_eq!(res, true);
+            assert!(res);
         }
     }
 
     #[test]
     fn test_no_parser() {
         if let Ok((_, res)) = no("NO".as_bytes()) {
-            assert_eq!(res, false);
+            assert!(!res);
         }
     }
 
     #[test]
     fn test_token_mol_id_parser() {
-        if let Ok((_, _res)) = mol_id("MOL_ID:".as_bytes()) {
-            assert!(true);
-        }
+        assert!(mol_id("MOL_ID:".as_bytes()).is_ok());
     }
 
     #[test]
@@ -509,9 +868,8 @@ mod test {
         match res {
             Ok((_, r)) => {
                 assert_eq!(r[0], "GLY");
-                assert!(true);
             }
-            Err(_err) => assert!(false),
+            Err(_err) => panic!(),
         }
     }
 
@@ -524,7 +882,7 @@ mod test {
             }
             Err(e) => {
                 println!("{:?}", e);
-                assert!(false);
+                panic!();
             }
         }
     }
@@ -545,7 +903,7 @@ mod test {
             }
             Err(e) => {
                 println!("{:?}", e);
-                assert!(false);
+                panic!();
             }
         }
     }
@@ -555,7 +913,7 @@ mod test {
         if let Ok((_, res)) = super::threedigit_integer(b"  7") {
             assert_eq!(7, res)
         } else {
-            assert!(false)
+            panic!()
         }
     }
 
@@ -567,19 +925,11 @@ mod test {
 
     #[test]
     fn two_space() {
-        if let Ok((_, _res)) = super::two_space(b"  ") {
-            assert!(true)
-        } else {
-            assert!(false)
-        }
+        assert!(super::two_space(b"  ").is_ok());
     }
 
     #[test]
     fn two_space_fail() {
-        if let Err(_e) = super::two_space(b" ") {
-            assert!(true)
-        } else {
-            assert!(false)
-        }
+        assert!(super::two_space(b" ").is_err());
     }
 }
@@ -1,66 +1,51 @@
 use super::ast::{pdb_file::*, types::*};
-use nom::{alt, complete, fold_many0, map, named, IResult};
+use nom::{error::ErrorKind, fold_many0, map, named, Err, IResult};
+use std::ops::Range;
 
 use super::{
+    anisou::anisou_record_parser,
+    atom::{atom_record_parser, hetatm_record_parser},
     author::author_record_parser,
     caveat::caveat_record_parser,
+    cispep::cispep_record_parser,
     compnd::cmpnd_token_parser,
+    conect::conect_record_parser,
+    cryst1::cryst1_record_parser,
     dbref::dbref_record_parser,
-    dbref1::dbref_partial_parser,
+    dbref1::{dbref2_record_parser, dbref_partial_parser},
+    error::PdbParseError,
     expdta::expdata_record_parser,
     header::header_parser,
+    helix::helix_record_parser,
+    het::{formul_record_parser, het_record_parser, hetnam_record_parser, hetsyn_record_parser},
     jrnl::{
         jrnl_author_record_parser, jrnl_doi_record_parser, jrnl_edit_record_parser,
         jrnl_pmid_record_parser, jrnl_publ_record_parser, jrnl_ref_record_parser,
         jrnl_refn_record_parser, jrnl_title_record_parser,
     },
     keywds::keywds_parser,
+    link::link_record_parser,
+    master::{end_record_parser, master_record_parser},
     mdltyp::mdltyp_record_parser,
+    model::{endmdl_record_parser, model_record_parser},
     modres::modres_record_parser,
     nummdl::nummdl_record_parser,
     obslte::obslte_record_parser,
     remark::remark_record_parser,
     revdat::revdat_record_parser,
     seqadv::seqadv_record_parser,
+    seqres::seqres_record_parser,
+    sheet::sheet_record_parser,
+    site::site_record_parser,
     source::source_token_parser,
     split::split_record_parser,
     sprsde::sprsde_record_parser,
+    ssbond::ssbond_record_parser,
+    ter::ter_record_parser,
     title::title_record_parser,
+    transformation::{mtrix_record_parser, origx_record_parser, scale_record_parser},
 };
 
-named!(
-    pdb_record_parser<Record>,
-    alt!(
-        complete!(header_parser)
-            | complete!(obslte_record_parser)
-            | complete!(title_record_parser)
-            | complete!(split_record_parser)
-            | complete!(caveat_record_parser)
-            | complete!(sprsde_record_parser)
-            | complete!(cmpnd_token_parser)
-            | complete!(source_token_parser)
-            | complete!(keywds_parser)
-            | complete!(expdata_record_parser)
-            | complete!(nummdl_record_parser)
-            | complete!(mdltyp_record_parser)
-            | complete!(author_record_parser)
-            | complete!(revdat_record_parser)
-            | complete!(jrnl_author_record_parser)
-            | complete!(jrnl_title_record_parser)
-            | complete!(jrnl_edit_record_parser)
-            | complete!(jrnl_refn_record_parser)
-            | complete!(jrnl_ref_record_parser)
-            | complete!(jrnl_publ_record_parser)
-            | complete!(jrnl_pmid_record_parser)
-            | complete!(jrnl_doi_record_parser)
-            | complete!(dbref_record_parser)
-            | complete!(dbref_partial_parser)
-            | complete!(seqadv_record_parser)
-            | complete!(remark_record_parser)
-            | complete!(modres_record_parser)
-    )
-);
-
 named!(
     pdb_records_parser<PdbFile<Vec<Record>>>,
     map!(
@@ -72,13 +57,214 @@ named!(
     )
 );
 
+type RecordParser = fn(&[u8]) -> IResult<&[u8], Record>;
+
+/// selects the parser responsible for a line by its record name given in columns 1 - 6.
+/// Continuation lines of records spanning several lines are read by the parser of the
+/// first line, so a continuation line found here is reported by that parser
+fn line_record_parser(line: &[u8]) -> Option<RecordParser> {
+    let line = &line[..line
+        .iter()
+        .position(|c| *c == b'\r' || *c == b'\n')
+        .unwrap_or(line.len())];
+    let record_name = String::from_utf8_lossy(&line[..line.len().min(6)]);
+    let parser: RecordParser = match record_name.trim_end() {
+        "HEADER" => header_parser,
+        "OBSLTE" => obslte_record_parser,
+        "TITLE" => title_record_parser,
+        "SPLIT" => split_record_parser,
+        "CAVEAT" => caveat_record_parser,
+        "SPRSDE" => sprsde_record_parser,
+        "COMPND" => cmpnd_token_parser,
+        "SOURCE" => source_token_parser,
+        "KEYWDS" => keywds_parser,
+        "EXPDTA" => expdata_record_parser,
+        "NUMMDL" => nummdl_record_parser,
+        "MDLTYP" => mdltyp_record_parser,
+        "AUTHOR" => author_record_parser,
+        "REVDAT" => revdat_record_parser,
+        "JRNL" => {
+            let sub_record = String::from_utf8_lossy(line.get(12..16).unwrap_or_default());
+            match sub_record.trim_end() {
+                "AUTH" => jrnl_author_record_parser,
+                "TITL" => jrnl_title_record_parser,
+                "EDIT" => jrnl_edit_record_parser,
+                "REFN" => jrnl_refn_record_parser,
+                "REF" => jrnl_ref_record_parser,
+                "PUBL" => jrnl_publ_record_parser,
+                "PMID" => jrnl_pmid_record_parser,
+                "DOI" => jrnl_doi_record_parser,
+                _ => return None,
+            }
+        }
+        "DBREF" => dbref_record_parser,
+        "DBREF1" => dbref_partial_parser,
+        "DBREF2" => dbref2_record_parser,
+        "SEQADV" => seqadv_record_parser,
+        "SEQRES" => seqres_record_parser,
+        "REMARK" => remark_record_parser,
+        "MODRES" => modres_record_parser,
+        "HET" => het_record_parser,
+        "HETNAM" => hetnam_record_parser,
+        "HETSYN" => hetsyn_record_parser,
+        "FORMUL" => formul_record_parser,
+        "HELIX" => helix_record_parser,
+        "SHEET" => sheet_record_parser,
+        "SSBOND" => ssbond_record_parser,
+        "LINK" => link_record_parser,
+        "CISPEP" => cispep_record_parser,
+        "SITE" => site_record_parser,
+        "CRYST1" => cryst1_record_parser,
+        "ORIGX1" | "ORIGX2" | "ORIGX3" => origx_record_parser,
+        "SCALE1" | "SCALE2" | "SCALE3" => scale_record_parser,
+        "MTRIX1" | "MTRIX2" | "MTRIX3" => mtrix_record_parser,
+        "ATOM" => atom_record_parser,
+        "HETATM" => hetatm_record_parser,
+        "ANISOU" => anisou_record_parser,
+        "TER" => ter_record_parser,
+        "MODEL" => model_record_parser,
+        "ENDMDL" => endmdl_record_parser,
+        "CONECT" => conect_record_parser,
+        "MASTER" => master_record_parser,
+        "END" => end_record_parser,
+        _ => return None,
+    };
+    Some(parser)
+}
+
+/// parses a single record with the parser selected by its record name
+fn pdb_record_parser(s: &[u8]) -> IResult<&[u8], Record> {
+    match line_record_parser(s) {
+        Some(parser) => match parser(s) {
+            Err(Err::Incomplete(_)) => Err(Err::Error((s, ErrorKind::Complete))),
+            result => result,
+        },
+        None => Err(Err::Error((s, ErrorKind::Alt))),
+    }
+}
+
+/// explains why parsing stopped at `remaining`
+fn parse_error(input: &[u8], remaining: &[u8]) -> PdbParseError {
+    match line_record_parser(remaining) {
+        None => PdbParseError::at(input, remaining, "unrecognized record name".to_owned()),
+        Some(parser) => match parser(remaining) {
+            Err(Err::Error((position, kind))) | Err(Err::Failure((position, kind))) => {
+                let reason = match (position.first(), kind) {
+                    (None, _) | (Some(b'\r'), _) | (Some(b'\n'), _) => {
+                        "unexpected end of line".to_owned()
+                    }
+                    (_, ErrorKind::Tag) => "unexpected text".to_owned(),
+                    (_, kind) => format!("invalid field ({})", kind.description()),
+                };
+                PdbParseError::at(input, position, reason)
+            }
+            Err(Err::Incomplete(_)) => {
+                PdbParseError::at(input, remaining, "unexpected end of input".to_owned())
+            }
+            Ok(_) => PdbParseError::at(
+                input,
+                remaining,
+                "record is not expected at this position".to_owned(),
+            ),
+        },
+    }
+}
+
 /// main parse function
 pub fn parse(s: &str) -> IResult<&[u8], PdbFile<Vec<Record>>> {
     pdb_records_parser(s.as_bytes())
 }
 
+/// Parses the whole input. Unlike [parse](fn.parse.html) fails with a
+/// [PdbParseError](error/struct.PdbParseError.html) pointing at the first line
+/// which can not be parsed instead of returning the records read so far.
+pub fn parse_strict(s: &str) -> Result<PdbFile<Vec<Record>>, PdbParseError> {
+    let mut input = s.as_bytes().to_vec();
+    if !input.ends_with(b"\n") {
+        input.push(b'\n');
+    }
+    match pdb_records_parser(&input) {
+        Ok((remaining, pdb)) if remaining.iter().all(u8::is_ascii_whitespace) => Ok(pdb),
+        Ok((remaining, _)) => Err(parse_error(&input, remaining)),
+        Err(_) => Err(parse_error(&input, &input)),
+    }
+}
+
+/// parses records one by one, keeping a `Record::Unknown` in place of every line
+/// which can not be parsed. Returns byte ranges of the records in `input` as well
+fn parse_records(input: &[u8]) -> (Vec<Record>, Vec<Range<usize>>, Vec<PdbParseError>) {
+    let mut records = Vec::new();
+    let mut ranges = Vec::new();
+    let mut diagnostics = Vec::new();
+    let mut remaining = input;
+    while !remaining.iter().all(u8::is_ascii_whitespace) {
+        let start = input.len() - remaining.len();
+        match pdb_record_parser(remaining) {
+            Ok((rest, record)) => {
+                records.push(record);
+                remaining = rest;
+            }
+            Err(_) => {
+                let line_length = remaining
+                    .iter()
+                    .position(|c| *c == b'\n')
+                    .unwrap_or(remaining.len());
+                let raw = String::from_utf8_lossy(&remaining[..line_length])
+                    .trim_end_matches('\r')
+                    .to_owned();
+                let line_number = input[..start].iter().filter(|c| **c == b'\n').count() + 1;
+                diagnostics.push(parse_error(input, remaining));
+                records.push(Record::Unknown { line_number, raw });
+                remaining = &remaining[(line_length + 1).min(remaining.len())..];
+            }
+        }
+        ranges.push(start..input.len() - remaining.len());
+    }
+    (records, ranges, diagnostics)
+}
+
+/// Parses the whole input skipping lines which can not be parsed. Each skipped
+/// line is kept in place as a `Record::Unknown` and reported with a
+/// [PdbParseError](error/struct.PdbParseError.html) diagnostic.
+pub fn parse_lenient(s: &str) -> (PdbFile<Vec<Record>>, Vec<PdbParseError>) {
+    let mut input = s.as_bytes().to_vec();
+    if !input.ends_with(b"\n") {
+        input.push(b'\n');
+    }
+    let (records, _, diagnostics) = parse_records(&input);
+    (records.to_pdb_file(), diagnostics)
+}
+
+/// Parses the whole input like [parse_lenient](fn.parse_lenient.html) and keeps the
+/// [SourceSpan](struct.SourceSpan.html) of every record. Writing an unmodified result
+/// reproduces the input byte for byte, records edited through
+/// [PdbFile::record_mut](struct.PdbFile.html#method.record_mut) are rendered again.
+pub fn parse_lossless(s: &str) -> (PdbFile<Vec<Record>>, Vec<PdbParseError>) {
+    let mut input = s.as_bytes().to_vec();
+    if !input.ends_with(b"\n") {
+        input.push(b'\n');
+    }
+    let (records, mut ranges, diagnostics) = parse_records(&input);
+    // trailing blank text belongs to the last record, the line ending
+    // appended above does not belong to any
+    if let Some(last) = ranges.last_mut() {
+        last.end = s.len();
+    }
+    let spans = ranges
+        .into_iter()
+        .map(|range| {
+            let range = range.start.min(s.len())..range.end.min(s.len());
+            Some(SourceSpan {
+                text: s[range.clone()].to_owned(),
+                range,
+            })
+        })
+        .collect();
+    (PdbFile::with_spans(records, spans), diagnostics)
+}
+
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use super::*;
     use std::{
         fs::File,
@@ -100,7 +286,7 @@ mod test {
         {
             assert_eq!(class, "PHOTOSYNTHESIS")
         } else {
-            assert!(false);
+            panic!();
         }
     }
 
@@ -118,7 +304,7 @@ mod test {
         {
             assert_eq!(class, "TRANSFERASE/TRANSFERASE")
         } else {
-            assert!(false);
+            panic!();
         }
     }
 
@@ -135,7 +321,7 @@ mod test {
         {
             assert_eq!(reps[0], "1MBP");
         } else {
-            assert!(false)
+            panic!()
         }
     }
 
@@ -155,7 +341,7 @@ mod test {
                 "RHIZOPUSPEPSIN COMPLEXED WITH REDUCED PEPTIDE INHIBITOR"
             )
         } else {
-            assert!(false)
+            panic!()
         }
     }
 
@@ -170,13 +356,13 @@ mod test {
         if let Record::Split(split) = splt {
             assert_eq!(split.id_codes[0], "1VOQ")
         } else {
-            assert!(false)
+            panic!()
         }
     }
 
     #[test]
     fn pdb_records_parser() {
-        if let Ok((_, mut res)) = super::pdb_records_parser(
+        if let Ok((_, res)) = super::pdb_records_parser(
             r#"HEADER    HYDROLASE                               20-APR-99   1CJY   
 TITLE     HUMAN CYTOSOLIC PHOSPHOLIPASE A2
 "#
@@ -194,13 +380,13 @@ TITLE     HUMAN CYTOSOLIC PHOSPHOLIPASE A2
                 assert_eq!(tit.title, "HUMAN CYTOSOLIC PHOSPHOLIPASE A2");
             }
         } else {
-            assert!(false);
+            panic!();
         }
     }
 
     #[test]
     fn ejg_header() {
-        if let Ok((_, mut res)) = super::pdb_records_parser(
+        if let Ok((_, res)) = super::pdb_records_parser(
             r#"HEADER    PLANT PROTEIN                           02-MAR-00   1EJG
 TITLE     CRAMBIN AT ULTRAHIGH RESOLUTION VALENCE ELECTRON DENSITY
 COMPND    MOL_ID: 1;
@@ -228,11 +414,116 @@ JRNL        DOI    10.1073/PNAS.97.7.3171
 
             assert_eq!(pubmedid.id, 10737790);
         } else {
-            assert!(false)
+            panic!()
+        }
+    }
+
+    #[test]
+    fn parse_strict() {
+        let err = super::parse_strict(
+            r#"HEADER    LIGASE                                  15-OCT-98   1BYI              
+ATOM      1  N   SER A   1      16.250  42.032  25.571  1.00 20.91           N  
+ATOM      2  CA  SER A   1      15.493  4O.784  25.264  1.00 21.24           C  
+"#,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(err.line_number, 3);
+        assert_eq!(err.record_name, "ATOM");
+        assert_eq!(err.columns, (39, 46));
+    }
+
+    #[test]
+    fn parse_strict_missing_row() {
+        let err = super::parse_strict(
+            r#"HEADER    LIGASE                                  15-OCT-98   1BYI              
+SCALE1      0.014006  0.000000  0.000000        0.00000                         
+SCALE3      0.000000  0.000000  0.011998        0.00000                         
+"#,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(err.line_number, 3);
+        assert_eq!(err.record_name, "SCALE3");
+        assert_eq!(err.columns, (6, 6));
+
+        let err = super::parse_strict(
+            "SCALE2      0.000000  0.020379  0.000000        0.00000                         \n",
+        )
+        .err()
+        .unwrap();
+        assert_eq!(err.columns, (6, 6));
+        assert_ne!(err.reason, "unrecognized record name");
+    }
+
+    #[test]
+    fn parse_strict_unknown_record() {
+        let err = super::parse_strict(
+            r#"HEADER    LIGASE                                  15-OCT-98   1BYI              
+XXXXXX    SOMETHING                                                             
+"#,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(err.line_number, 2);
+        assert_eq!(err.record_name, "XXXXXX");
+        assert_eq!(err.columns, (1, 6));
+        assert_eq!(err.reason, "unrecognized record name");
+    }
+
+    #[test]
+    fn parse_strict_without_trailing_newline() {
+        let res = super::parse_strict(
+            "HEADER    LIGASE                                  15-OCT-98   1BYI              ",
+        )
+        .unwrap();
+        assert_eq!(res.header().header().unwrap().id_code, "1BYI");
+    }
+
+    #[test]
+    fn parse_lenient() {
+        let (res, diagnostics) = super::parse_lenient(
+            r#"HEADER    LIGASE                                  15-OCT-98   1BYI              
+MODRES 2R0L ASN A   74  ASN  GLYCOSYLATION SITE  
+MODRES 2R0L ASN A       ASN  GLYCOSYLATION SITE  
+ATOM      1  N   SER A   1      16.250  42.032  25.571  1.00 20.91           N  
+"#,
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line_number, 3);
+        assert_eq!(diagnostics[0].record_name, "MODRES");
+        assert_eq!(res.coordinates().atoms().count(), 1);
+        assert_eq!(res.header().header().unwrap().id_code, "1BYI");
+    }
+
+    #[test]
+    fn parse_lossless() {
+        let text = "HEADER    LIGASE                                  15-OCT-98   1BYI\r
+TITLE     STRUCTURE OF APO-DETHIOBIOTIN SYNTHASE AT 0.97 ANGSTROMS RESOLUTION   \r
+CRYST1   72.900   49.100   61.400  90.00 107.60  90.00 C 1 2 1       4          \r
+ATOM      1  N   SER A   1      16.250  42.032  25.571  1.00 20.91           N\r
+\r
+END";
+        let (mut res, diagnostics) = super::parse_lossless(text);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(res.to_pdb_string().unwrap(), text);
+        assert_eq!(
+            res.span(2).unwrap().lines().next().unwrap(),
+            "CRYST1   72.900   49.100   61.400  90.00 107.60  90.00 C 1 2 1       4          "
+        );
+
+        if let Some(Record::Header(header)) = res.record_mut(0) {
+            header.classification = "HYDROLASE".to_owned();
         }
+        let written = res.to_pdb_string().unwrap();
+        assert!(written.starts_with(&format!(
+            "{:<80}\nTITLE",
+            "HEADER    HYDROLASE                               15-OCT-98   1BYI"
+        )));
+        assert!(written.ends_with(&text[text.find("TITLE").unwrap() + 5..]));
     }
 
-    fn get_test_file_path(file_name: &str) -> PathBuf {
+    pub(crate) fn get_test_file_path(file_name: &str) -> PathBuf {
         let mut current_file_path = PathBuf::from(file!());
         current_file_path.pop();
         current_file_path.pop();
@@ -241,7 +532,7 @@ JRNL        DOI    10.1073/PNAS.97.7.3171
         current_file_path
     }
 
-    fn read_file(path: &PathBuf) -> String {
+    pub(crate) fn read_file(path: &PathBuf) -> String {
         let file = File::open(path).unwrap();
         let mut buf_reader = BufReader::new(file);
         let mut contents = String::new();
@@ -259,7 +550,7 @@ JRNL        DOI    10.1073/PNAS.97.7.3171
         let contents = read_file(&test_file_path);
         let expected = read_file(&expected_file_path);
         let expected_val: Value = serde_json::from_str(&expected).unwrap();
-        let mut pdb_parsed = super::pdb_records_parser(contents.as_bytes()).unwrap().1;
+        let pdb_parsed = super::pdb_records_parser(contents.as_bytes()).unwrap().1;
 
         assert_eq!(
             expected_val["header.classification"],
@@ -1,11 +1,509 @@
+/*!
+Contains parsers related to [Remark](http://www.wwpdb.org/documentation/file-format-content/format33/remarks.html) records.
+REMARK records present experimental details, annotations, comments, and information not included
+in other records. Each remark is identified by its number and consists of free text lines.
+*/
 use super::{ast::types::*, primitive::*};
-use nom::character::complete::line_ending;
-use nom::{do_parse, named};
+use nom::character::complete::{line_ending, space0};
+use nom::{alt, do_parse, map, map_res, named, tag, take, take_till, IResult};
+
+use std::{collections::BTreeMap, str, str::FromStr};
 
 named!(
-    pub remark_record_parser<Record>,
+    remark_line_parser<(u32, String)>,
     do_parse!(
         remark
-        >> till_line_ending
-        >> line_ending
-        >> (Record::Remark)));
+            >> take!(1)
+            >> number: threedigit_integer
+            >> text: map_res!(till_line_ending, str::from_utf8)
+            >> line_ending
+            >> ((
+                number,
+                text.get(1..).unwrap_or_default().trim_end().to_owned()
+            ))
+    )
+);
+
+/// Parses consecutive REMARK lines having the same remark number. If successfull returns
+/// [Record](../ast/types/enum.Record.html) variant containing [Remark](../ast/types/struct.Remark.html)
+/// instance. Lines are kept without the leading record name and remark number, blank lines included.
+///
+/// Record structure :
+///
+/// | COLUMNS  | DATA  TYPE   | FIELD      | DEFINITION                                |
+/// |----------|--------------|------------|-------------------------------------------|
+/// | 1 -  6   | Record name  | "REMARK"   |                                           |
+/// | 8 - 10   | Integer      | remarkNum  | Remark  number.                           |
+/// | 12 - 79  | LString      | text       | Free text.                                |
+pub fn remark_record_parser(s: &[u8]) -> IResult<&[u8], Record> {
+    let (mut rest, (number, line)) = remark_line_parser(s)?;
+    let mut lines = vec![line];
+    while let Ok((next, (next_number, line))) = remark_line_parser(rest) {
+        if next_number != number {
+            break;
+        }
+        lines.push(line);
+        rest = next;
+    }
+    Ok((rest, Record::Remark(Remark { number, lines })))
+}
+
+named!(
+    #[doc=r#"Parses the resolution line of REMARK 2 given without the leading record name and remark number.
+
+```
+# use patoz::{remark::resolution_parser, Resolution};
+assert_eq!(
+    Ok((&b""[..], Resolution::Angstroms(1.74))),
+    resolution_parser(b"RESOLUTION.    1.74 ANGSTROMS.")
+);
+assert_eq!(
+    Ok((&b"."[..], Resolution::NotApplicable)),
+    resolution_parser(b"RESOLUTION. NOT APPLICABLE.")
+);
+```
+"#],
+    pub resolution_parser<Resolution>,
+    do_parse!(
+        tag!("RESOLUTION.")
+            >> space0
+            >> res: alt!(
+                map!(tag!("NOT APPLICABLE"), |_| Resolution::NotApplicable)
+                    | do_parse!(
+                        value: map_res!(
+                            map_res!(take_till!(|c| c == b' '), str::from_utf8),
+                            f64::from_str
+                        ) >> space0
+                            >> tag!("ANGSTROMS.")
+                            >> (Resolution::Angstroms(value))
+                    )
+            )
+            >> (res)
+    )
+);
+
+/// returns resolution given in REMARK 2 lines
+pub fn resolution(lines: &[&str]) -> Option<Resolution> {
+    lines
+        .iter()
+        .find_map(|l| resolution_parser(l.trim().as_bytes()).ok())
+        .map(|(_, res)| res)
+}
+
+/// splits a REMARK 3 line of the form `KEY (UNIT) : VALUE` into its key with
+/// whitespace collapsed and its value. `NULL` values are returned as `None`
+fn refinement_entry(line: &str) -> Option<(String, Option<&str>)> {
+    let separator = line.find(':')?;
+    let key = line[..separator]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let value = line[separator + 1..].trim();
+    Some((
+        key,
+        if value.is_empty() || value == "NULL" {
+            None
+        } else {
+            Some(value)
+        },
+    ))
+}
+
+/// Collects refinement statistics from REMARK 3 lines. Key names differ between
+/// refinement programs, so the first entry matching any of the known keys is used.
+/// Returns `None` if the lines do not contain any of the statistics.
+pub fn refinement(lines: &[&str]) -> Option<Refinement> {
+    let entries = lines
+        .iter()
+        .filter_map(|l| refinement_entry(l))
+        .collect::<Vec<_>>();
+    let value = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| entries.iter().find(|(k, _)| k == key).and_then(|(_, v)| *v))
+    };
+    let real = |keys: &[&str]| value(keys).and_then(|v| v.parse::<f64>().ok());
+
+    let refinement = Refinement {
+        program: value(&["PROGRAM"]).map(str::to_owned),
+        resolution_high: real(&["RESOLUTION RANGE HIGH (ANGSTROMS)"]),
+        resolution_low: real(&["RESOLUTION RANGE LOW (ANGSTROMS)"]),
+        number_of_reflections: value(&[
+            "NUMBER OF REFLECTIONS",
+            "TOTAL NUMBER OF REFLECTIONS (NO CUTOFF)",
+        ])
+        .and_then(|v| v.parse::<u32>().ok()),
+        r_work: real(&["R VALUE (WORKING SET)", "R VALUE (WORKING SET, NO CUTOFF)"]),
+        r_free: real(&["FREE R VALUE", "FREE R VALUE (NO CUTOFF)"]),
+        wilson_b_value: real(&["FROM WILSON PLOT (A**2)"]),
+        mean_b_value: real(&["MEAN B VALUE (OVERALL, A**2)"]),
+    };
+    if refinement == Refinement::default() {
+        None
+    } else {
+        Some(refinement)
+    }
+}
+
+/// Collects biological assemblies from REMARK 350 lines. Every BIOMOLECULE starts
+/// a new assembly, `APPLY THE FOLLOWING TO CHAINS` lines and their `AND CHAINS`
+/// continuations select the chains for the BIOMT operators following them.
+pub fn biological_assemblies(lines: &[&str]) -> Vec<BiologicalAssembly> {
+    let chain_list = |list: &str| {
+        list.split(',')
+            .filter_map(|c| c.trim().chars().next())
+            .collect::<Vec<_>>()
+    };
+    let mut assemblies: Vec<BiologicalAssembly> = Vec::new();
+    let mut chains = Vec::new();
+    // row expected next for the operator started by the last BIOMT1 line
+    let mut next_row = None;
+    for line in lines.iter().map(|l| l.trim()) {
+        if let Some(number) = line.strip_prefix("BIOMOLECULE:") {
+            assemblies.push(BiologicalAssembly {
+                number: number.trim().parse().unwrap_or_default(),
+                operators: Vec::new(),
+            });
+            next_row = None;
+        } else if let Some(list) = line.strip_prefix("APPLY THE FOLLOWING TO CHAINS:") {
+            chains = chain_list(list);
+        } else if let Some(list) = line.strip_prefix("AND CHAINS:") {
+            chains.extend(chain_list(list));
+        } else if line.starts_with("BIOMT") {
+            let row = line
+                .get(5..6)
+                .and_then(|r| r.parse::<usize>().ok())
+                .filter(|r| (1..=3).contains(r));
+            let fields = line
+                .get(6..)
+                .unwrap_or_default()
+                .split_whitespace()
+                .collect::<Vec<_>>();
+            let values = fields
+                .get(1..5)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|v| v.parse::<f64>().ok())
+                .collect::<Vec<_>>();
+            let serial = fields.first().and_then(|s| s.parse::<u32>().ok());
+            if let (Some(row), Some(serial), Some(assembly), 4) =
+                (row, serial, assemblies.last_mut(), values.len())
+            {
+                if row == 1 {
+                    assembly.operators.push(AssemblyOperator {
+                        serial,
+                        chains: chains.clone(),
+                        matrix: Matrix3x4::identity(),
+                    });
+                    next_row = Some(1);
+                }
+                // rows 2 and 3 are only taken if they continue the operator of the last row 1
+                match assembly.operators.last_mut() {
+                    Some(operator) if next_row == Some(row) && operator.serial == serial => {
+                        operator.matrix.rows[row - 1].copy_from_slice(&values);
+                        next_row = Some(row + 1);
+                    }
+                    _ => next_row = None,
+                }
+            }
+        }
+    }
+    assemblies
+}
+
+/// Collects site descriptions from REMARK 800 lines keyed by site identifier. Every
+/// `SITE_IDENTIFIER` starts a new description, lines following `SITE_DESCRIPTION`
+/// up to the next blank line continue the description.
+pub fn site_descriptions(lines: &[&str]) -> BTreeMap<String, SiteDescription> {
+    let mut descriptions: Vec<SiteDescription> = Vec::new();
+    let mut in_description = false;
+    for line in lines.iter().map(|l| l.trim()) {
+        if let Some(site_id) = line.strip_prefix("SITE_IDENTIFIER:") {
+            descriptions.push(SiteDescription {
+                site_id: site_id.trim().to_owned(),
+                ..Default::default()
+            });
+            in_description = false;
+        } else if let Some(description) = descriptions.last_mut() {
+            if let Some(code) = line.strip_prefix("EVIDENCE_CODE:") {
+                description.evidence_code = Some(code.trim().to_owned());
+                in_description = false;
+            } else if let Some(text) = line.strip_prefix("SITE_DESCRIPTION:") {
+                description.description = Some(text.trim().to_owned());
+                in_description = true;
+            } else if line.is_empty() {
+                in_description = false;
+            } else if in_description {
+                if let Some(text) = description.description.as_mut() {
+                    text.push(' ');
+                    text.push_str(line);
+                }
+            }
+        }
+    }
+    descriptions
+        .into_iter()
+        .map(|d| (d.site_id.clone(), d))
+        .collect()
+}
+
+/// reads model, residue name, chain identifier, sequence number and insertion code
+/// of a REMARK 465 or REMARK 470 line. `sequence_end` is the column after the
+/// sequence number, where the insertion code is. Blank model numbers refer to model 1
+fn residue_columns(
+    line: &str,
+    sequence_end: usize,
+) -> Option<(u32, String, char, i32, Option<char>)> {
+    let column = |from: usize, to: usize| line.get(from..to.min(line.len())).map(str::trim);
+    let model = match column(0, 3)? {
+        "" => 1,
+        model => model.parse().ok()?,
+    };
+    let residue_name = column(4, 7).filter(|r| !r.is_empty())?;
+    let chain_id = line.get(8..9)?.chars().next()?;
+    let sequence_number = column(9, sequence_end)?.parse().ok()?;
+    let insertion_code = line
+        .get(sequence_end..sequence_end + 1)
+        .and_then(|c| c.chars().next())
+        .filter(|c| *c != ' ');
+    Some((
+        model,
+        residue_name.to_owned(),
+        chain_id,
+        sequence_number,
+        insertion_code,
+    ))
+}
+
+/// Collects missing residues from REMARK 465 lines. Residues are listed after the
+/// `M RES C SSSEQI` heading.
+///
+/// | COLUMNS  | FIELD      | DEFINITION                        |
+/// |----------|------------|-----------------------------------|
+/// | 12 - 14  | M          | Model number.                     |
+/// | 16 - 18  | RES        | Residue name.                     |
+/// | 20       | C          | Chain identifier.                 |
+/// | 22 - 26  | SSSEQ      | Residue sequence number.          |
+/// | 27       | I          | Insertion code.                   |
+pub fn missing_residues(lines: &[&str]) -> BTreeMap<ResidueLocation, MissingResidue> {
+    lines
+        .iter()
+        .skip_while(|l| !l.contains("RES C SSSEQI"))
+        .skip(1)
+        .filter_map(|l| residue_columns(l, 15))
+        .map(
+            |(model, residue_name, chain_id, sequence_number, insertion_code)| {
+                (
+                    (model, chain_id, sequence_number, insertion_code),
+                    MissingResidue {
+                        model,
+                        residue_name,
+                        chain_id,
+                        sequence_number,
+                        insertion_code,
+                    },
+                )
+            },
+        )
+        .collect()
+}
+
+/// Collects residues with missing atoms from REMARK 470 lines. Residues are listed
+/// after the `M RES CSSEQI  ATOMS` heading.
+///
+/// | COLUMNS  | FIELD      | DEFINITION                        |
+/// |----------|------------|-----------------------------------|
+/// | 12 - 14  | M          | Model number.                     |
+/// | 16 - 18  | RES        | Residue name.                     |
+/// | 20       | C          | Chain identifier.                 |
+/// | 21 - 24  | SSEQ       | Residue sequence number.          |
+/// | 25       | I          | Insertion code.                   |
+/// | 26 - 79  | ATOMS      | Names of the missing atoms.       |
+pub fn missing_atoms(lines: &[&str]) -> BTreeMap<ResidueLocation, MissingAtoms> {
+    lines
+        .iter()
+        .skip_while(|l| !l.contains("RES CSSEQI"))
+        .skip(1)
+        .filter_map(|l| {
+            let (model, residue_name, chain_id, sequence_number, insertion_code) =
+                residue_columns(l, 13)?;
+            Some((
+                (model, chain_id, sequence_number, insertion_code),
+                MissingAtoms {
+                    model,
+                    residue_name,
+                    chain_id,
+                    sequence_number,
+                    insertion_code,
+                    atoms: l
+                        .get(14..)
+                        .unwrap_or_default()
+                        .split_whitespace()
+                        .map(str::to_owned)
+                        .collect(),
+                },
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn remark() {
+        if let Ok((rest, Record::Remark(res))) = remark_record_parser(
+            r#"REMARK   2                                                                      
+REMARK   2 RESOLUTION.    0.97 ANGSTROMS.                                       
+REMARK   3                                                                      
+"#
+            .as_bytes(),
+        ) {
+            assert_eq!(res.number, 2);
+            assert_eq!(res.lines, vec!["", "RESOLUTION.    0.97 ANGSTROMS."]);
+            assert!(rest.starts_with(b"REMARK   3"));
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn refinement() {
+        let res = super::refinement(&[
+            "",
+            "REFINEMENT.",
+            "  PROGRAM     : REFMAC 5.8.0158",
+            "",
+            " DATA USED IN REFINEMENT.",
+            "  RESOLUTION RANGE HIGH (ANGSTROMS) : 1.74",
+            "  RESOLUTION RANGE LOW  (ANGSTROMS) : 46.52",
+            "  DATA CUTOFF            (SIGMA(F)) : NULL",
+            "  NUMBER OF REFLECTIONS             : 29154",
+            "",
+            " FIT TO DATA USED IN REFINEMENT.",
+            "  R VALUE     (WORKING + TEST SET) : 0.18950",
+            "  R VALUE            (WORKING SET) : 0.18736",
+            "  FREE R VALUE                     : 0.22941",
+            "",
+            " B VALUES.",
+            "  FROM WILSON PLOT           (A**2) : NULL",
+            "  MEAN B VALUE      (OVERALL, A**2) : 24.95",
+        ])
+        .unwrap();
+        assert_eq!(res.program, Some("REFMAC 5.8.0158".to_owned()));
+        assert_eq!(res.resolution_high, Some(1.74));
+        assert_eq!(res.resolution_low, Some(46.52));
+        assert_eq!(res.number_of_reflections, Some(29154));
+        assert_eq!(res.r_work, Some(0.18736));
+        assert_eq!(res.r_free, Some(0.22941));
+        assert_eq!(res.wilson_b_value, None);
+        assert_eq!(res.mean_b_value, Some(24.95));
+    }
+
+    #[test]
+    fn site_descriptions() {
+        let lines = vec![
+            "",
+            "SITE",
+            "SITE_IDENTIFIER: CIC",
+            "EVIDENCE_CODE: AUTHOR",
+            "SITE_DESCRIPTION: CATALYTIC RESIDUES",
+            "",
+            "SITE_IDENTIFIER: AC1",
+            "EVIDENCE_CODE: SOFTWARE",
+            "SITE_DESCRIPTION: BINDING SITE FOR RESIDUE MAN A",
+            "328",
+        ];
+        let descriptions = super::site_descriptions(&lines);
+        assert_eq!(descriptions.len(), 2);
+        assert_eq!(descriptions["CIC"].evidence_code.as_deref(), Some("AUTHOR"));
+        assert_eq!(
+            descriptions["AC1"].description.as_deref(),
+            Some("BINDING SITE FOR RESIDUE MAN A 328")
+        );
+    }
+
+    #[test]
+    fn biological_assemblies() {
+        let res = super::biological_assemblies(&[
+            "BIOMOLECULE: 1",
+            "AUTHOR DETERMINED BIOLOGICAL UNIT: DIMERIC",
+            "APPLY THE FOLLOWING TO CHAINS: A, B,",
+            "                   AND CHAINS: C",
+            "  BIOMT1   1  1.000000  0.000000  0.000000        0.00000",
+            "  BIOMT2   1  0.000000  1.000000  0.000000        0.00000",
+            "  BIOMT3   1  0.000000  0.000000  1.000000        0.00000",
+            "  BIOMT1   2 -1.000000  0.000000  0.000000       72.90000",
+            "  BIOMT2   2  0.000000  1.000000  0.000000        0.00000",
+            "  BIOMT3   2  0.000000  0.000000 -1.000000        0.00000",
+            "",
+            "BIOMOLECULE: 2",
+            "APPLY THE FOLLOWING TO CHAINS: D",
+            "  BIOMT1   1  1.000000  0.000000  0.000000        0.00000",
+            "  BIOMT2   1  0.000000  1.000000  0.000000        0.00000",
+            "  BIOMT3   1  0.000000  0.000000  1.000000        0.00000",
+        ]);
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].operators.len(), 2);
+        assert_eq!(res[0].operators[1].chains, vec!['A', 'B', 'C']);
+        assert_eq!(res[0].operators[1].matrix.rows[0], [-1.0, 0.0, 0.0, 72.9]);
+        assert_eq!(
+            res[0].operators[1].matrix.apply([1.0, 2.0, 3.0]),
+            [71.9, 2.0, -3.0]
+        );
+        assert_eq!(res[1].number, 2);
+        assert_eq!(res[1].operators[0].chains, vec!['D']);
+    }
+
+    #[test]
+    fn malformed_biomt() {
+        let res = super::biological_assemblies(&[
+            "BIOMOLECULE: 1",
+            "APPLY THE FOLLOWING TO CHAINS: A",
+            "  BIOMT1   1  1.000000  0.000000  0.000000        0.00000",
+            "  BIOMT2   1  0.000000  1.000000  0.000000        0.00000",
+            "  BIOMT3   1  0.000000  0.000000  1.000000        0.00000",
+            "  BIOMT",
+            "  BIOMT\u{e9}",
+            "  BIOMT2   2  0.000000 -1.000000  0.000000        0.00000",
+            "  BIOMT3   2  0.000000  0.000000 -1.000000        0.00000",
+        ]);
+        assert_eq!(res[0].operators.len(), 1);
+        assert_eq!(res[0].operators[0].matrix, Matrix3x4::identity());
+    }
+
+    #[test]
+    fn missing_residues() {
+        let res = super::missing_residues(&[
+            "MISSING RESIDUES",
+            "THE FOLLOWING RESIDUES WERE NOT LOCATED IN THE",
+            "EXPERIMENT. (M=MODEL NUMBER; RES=RESIDUE NAME; C=CHAIN",
+            "IDENTIFIER; SSSEQ=SEQUENCE NUMBER; I=INSERTION CODE.)",
+            "",
+            "  M RES C SSSEQI",
+            "    MET A    -1",
+            "    GLY A    12A",
+            "  2 SER B  1001",
+        ]);
+        assert_eq!(res.len(), 3);
+        assert_eq!(res[&(1, 'A', -1, None)].residue_name, "MET");
+        assert_eq!(res[&(1, 'A', 12, Some('A'))].residue_name, "GLY");
+        assert_eq!(res[&(2, 'B', 1001, None)].model, 2);
+    }
+
+    #[test]
+    fn missing_atoms() {
+        let res = super::missing_atoms(&[
+            "MISSING ATOM",
+            "THE FOLLOWING RESIDUES HAVE MISSING ATOMS (M=MODEL NUMBER;",
+            "RES=RESIDUE NAME; C=CHAIN IDENTIFIER; SSEQ=SEQUENCE NUMBER;",
+            "I=INSERTION CODE):",
+            "  M RES CSSEQI  ATOMS",
+            "    LYS A  12    CG   CD   CE   NZ",
+            "    GLU B1012B   OE1  OE2",
+        ]);
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[&(1, 'A', 12, None)].atoms, vec!["CG", "CD", "CE", "NZ"]);
+        assert_eq!(res[&(1, 'B', 1012, Some('B'))].atoms, vec!["OE1", "OE2"]);
+    }
+}
@@ -1,6 +1,6 @@
 use super::{ast::types::*, primitive::*};
 use nom::{
-    character::complete::{anychar, space1},
+    character::complete::{anychar, line_ending, space1},
     do_parse, named, opt, tag,
 };
 
@@ -15,7 +15,7 @@ named!(
         >> space1
         >> chain_id : anychar
         >> tag!(" ")
-        >> sequence_number : fourdigit_integer
+        >> sequence_number : fourdigit_hybrid36
         >> insertion_code : opt!(anychar)
         >> space1
         >> database : alphanum_word
@@ -28,6 +28,7 @@ named!(
         >> space1
         >> conflict : alphanum_word_with_spaces_inside
         >> till_line_ending
+        >> line_ending
         >> (Record::Seqadv(Seqadv{
             idcode,
             conflicting_residue,
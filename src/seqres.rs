@@ -1,10 +1,7 @@
 use super::{ast::types::*, primitive::*};
-use nom::{
-    character::complete::{anychar, space1},
-    do_parse, many0, map, named, opt,
-};
+use nom::{character::complete::line_ending, do_parse, map_res, named, take, IResult};
 
-use itertools::Itertools;
+use std::str;
 
 #[allow(dead_code)]
 pub struct SeqresLine {
@@ -43,39 +40,89 @@ named!(#[doc=r#"Parses a line of [SEQRES](http://www.wwpdb.org/documentation/fil
     pub seqres_line_parser<SeqresLine>,
     do_parse!(
         seqres
-            >> space1
-            >> serial_number: integer
-            >> space1
-            >> chain_id: opt!(anychar)
-            >> space1
-            >> num_res: integer
-            >> residues: residue_list_parser
+            >> take!(1)
+            >> serial_number: threedigit_integer
+            >> take!(1)
+            >> chain_id: optional_char
+            >> take!(1)
+            >> num_res: fourdigit_integer
+            >> residues: map_res!(till_line_ending, str::from_utf8)
+            >> line_ending
             >> (SeqresLine {
                 serial_number,
                 chain_id,
                 num_res,
-                residues,
+                residues: residues.split_whitespace().map(str::to_owned).collect(),
             })
     )
 );
 
-named!(seqres_parser<Vec<SeqresLine>>, many0!(seqres_line_parser));
+/// Parses consecutive SEQRES lines of a single chain. If successfull returns
+/// [Record](../ast/types/enum.Record.html) variant containing [Seqres](../ast/types/struct.Seqres.html)
+/// instance. A numRes of a following line differing from numRes of the first line is kept
+/// as `conflicting_num_res` and reported by [sequences](../struct.PrimaryStructure.html#method.sequences).
+pub fn seqres_record_parser(s: &[u8]) -> IResult<&[u8], Record> {
+    let (mut rest, first) = seqres_line_parser(s)?;
+    let mut residues = first.residues;
+    let mut conflicting_num_res = None;
+    let mut source_lines = 1;
+    while let Ok((next, line)) = seqres_line_parser(rest) {
+        if line.chain_id != first.chain_id {
+            break;
+        }
+        if line.num_res != first.num_res {
+            conflicting_num_res = conflicting_num_res.or(Some(line.num_res));
+        }
+        residues.extend(line.residues);
+        source_lines += 1;
+        rest = next;
+    }
+    Ok((
+        rest,
+        Record::Seqres(Seqres {
+            chain_id: first.chain_id,
+            num_res: first.num_res,
+            residues,
+            conflicting_num_res,
+            source_lines,
+        }),
+    ))
+}
 
-named!(
-    seqres_record_parser<Vec<Record>>,
-    map!(seqres_parser, |seqres: Vec<SeqresLine>| {
-        seqres
-            .into_iter()
-            .group_by(|a| a.chain_id)
-            .into_iter()
-            .map(|(k, v)| {
-                Record::Seqres(Seqres {
-                    chain_id: k,
-                    residues: v.fold(Vec::new(), |v: Vec<String>, sr: SeqresLine| {
-                        v.into_iter().chain(sr.residues).collect()
-                    }),
-                })
-            })
-            .collect::<Vec<_>>()
-    })
-);
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn seqres() {
+        let lines = r#"SEQRES   1 A   15  SER LYS ARG TYR PHE VAL THR GLY THR ASP THR GLU VAL          
+SEQRES   2 A   15  GLY LYS                                                      
+SEQRES   1 B    1  HOH                                                          
+"#;
+        if let Ok((rest, Record::Seqres(res))) = seqres_record_parser(lines.as_bytes()) {
+            assert_eq!(res.chain_id, Some('A'));
+            assert_eq!(res.num_res, 15);
+            assert_eq!(res.residues.len(), 15);
+            assert_eq!(res.residues[14], "LYS");
+            assert_eq!(res.source_lines, 2);
+            assert!(rest.starts_with(b"SEQRES   1 B"));
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn seqres_num_res_mismatch() {
+        let lines = r#"SEQRES   1 A   15  SER LYS ARG TYR PHE VAL THR GLY THR ASP THR GLU VAL
+SEQRES   2 A   16  GLY LYS
+"#;
+        if let Ok((rest, Record::Seqres(res))) = seqres_record_parser(lines.as_bytes()) {
+            assert!(rest.is_empty());
+            assert_eq!(res.num_res, 15);
+            assert_eq!(res.conflicting_num_res, Some(16));
+            assert_eq!(res.residues.len(), 15);
+        } else {
+            panic!()
+        }
+    }
+}
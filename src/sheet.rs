@@ -0,0 +1,143 @@
+/*!
+Contains parsers related to [Sheet](http://www.wwpdb.org/documentation/file-format-content/format33/sect5.html#SHEET) records.
+SHEET records are used to identify the position of sheets in the molecule. Every strand of a sheet is given
+in a separate record together with its registration to the previous strand.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{
+    character::complete::line_ending, complete, do_parse, map, map_opt, named, opt, take, take_str,
+};
+
+use std::str;
+
+named!(
+    strand_sense_parser<StrandSense>,
+    map_opt!(take_str!(2), |s: &str| match s.trim() {
+        "0" => Some(StrandSense::First),
+        "1" => Some(StrandSense::Parallel),
+        "-1" => Some(StrandSense::AntiParallel),
+        _ => None,
+    })
+);
+
+named!(
+    registration_parser<Registration>,
+    do_parse!(
+        take!(1)
+            >> current: atom_reference
+            >> take!(1)
+            >> previous: atom_reference
+            >> (Registration { current, previous })
+    )
+);
+
+named!(
+    #[doc=r#"Parses SHEET record. If successfull returns [Record](../ast/types/enum.Record.html) variant
+containing [Sheet](../ast/types/struct.Sheet.html) instance. Registration fields are blank for the
+first strand of a sheet, in which case `registration` is `None`.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD       | DEFINITION                                         |
+|----------|--------------|-------------|----------------------------------------------------|
+| 1 -  6   | Record name  | "SHEET "    |                                                    |
+| 8 - 10   | Integer      | strand      | Strand  number which starts at 1 for each          |
+|          |              |             | strand within a sheet and increases by one.        |
+| 12 - 14  | LString(3)   | sheetID     | Sheet  identifier.                                 |
+| 15 - 16  | Integer      | numStrands  | Number  of strands in sheet.                       |
+| 18 - 20  | Residue name | initResName | Residue  name of initial residue.                  |
+| 22       | Character    | initChainID | Chain identifier of initial residue in strand.     |
+| 23 - 26  | Integer      | initSeqNum  | Sequence number of initial residue in strand.      |
+| 27       | AChar        | initICode   | Insertion code of initial residue in  strand.      |
+| 29 - 31  | Residue name | endResName  | Residue name of terminal residue.                  |
+| 33       | Character    | endChainID  | Chain identifier of terminal residue.              |
+| 34 - 37  | Integer      | endSeqNum   | Sequence number of terminal residue.               |
+| 38       | AChar        | endICode    | Insertion code of terminal residue.                |
+| 39 - 40  | Integer      | sense       | Sense of strand with respect to previous           |
+|          |              |             | strand in the sheet. 0 if first strand,            |
+|          |              |             | 1 if  parallel,and -1 if anti-parallel.            |
+| 42 - 45  | Atom         | curAtom     | Registration.  Atom name in current strand.        |
+| 46 - 48  | Residue name | curResName  | Registration.  Residue name in current strand      |
+| 50       | Character    | curChainId  | Registration. Chain identifier in                  |
+|          |              |             | current strand.                                    |
+| 51 - 54  | Integer      | curResSeq   | Registration.  Residue sequence number             |
+|          |              |             | in current strand.                                 |
+| 55       | AChar        | curICode    | Registration. Insertion code in                    |
+|          |              |             | current strand.                                    |
+| 57 - 60  | Atom         | prevAtom    | Registration.  Atom name in previous strand.       |
+| 61 - 63  | Residue name | prevResName | Registration.  Residue name in                     |
+|          |              |             | previous strand.                                   |
+| 65       | Character    | prevChainId | Registration.  Chain identifier in                 |
+|          |              |             | previous  strand.                                  |
+| 66 - 69  | Integer      | prevResSeq  | Registration. Residue sequence number              |
+|          |              |             | in previous strand.                                |
+| 70       | AChar        | prevICode   | Registration.  Insertion code in                   |
+|          |              |             | previous strand.                                   |
+"#],
+    pub sheet_record_parser<Record>,
+    do_parse!(
+        sheet
+            >> take!(2)
+            >> strand: threedigit_integer
+            >> take!(1)
+            >> sheet_id: map!(take_str!(3), str::trim)
+            >> num_strands: twodigit_integer
+            >> take!(1)
+            >> start: residue_reference
+            >> take!(1)
+            >> end: residue_reference
+            >> sense: strand_sense_parser
+            >> registration: opt!(complete!(registration_parser))
+            >> till_line_ending
+            >> line_ending
+            >> (Record::Sheet(Sheet {
+                strand,
+                sheet_id: sheet_id.to_owned(),
+                num_strands,
+                start,
+                end,
+                sense,
+                registration,
+            }))
+    )
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sheet() {
+        if let Ok((_, Record::Sheet(res))) = sheet_record_parser(
+            "SHEET    1   A 8 THR A  26  LEU A  30  0                                        \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.strand, 1);
+            assert_eq!(res.sheet_id, "A");
+            assert_eq!(res.num_strands, 8);
+            assert_eq!(res.start.residue_name, "THR");
+            assert_eq!(res.end.sequence_number, 30);
+            assert_eq!(res.sense, StrandSense::First);
+            assert!(res.registration.is_none());
+        } else {
+            panic!()
+        }
+
+        if let Ok((_, Record::Sheet(res))) = sheet_record_parser(
+            "SHEET    2   A 8 TYR A  17  ILE A  23 -1  N  ILE A  23   O  THR A  26           \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.sense, StrandSense::AntiParallel);
+            let registration = res.registration.unwrap();
+            assert_eq!(registration.current.name, "N");
+            assert_eq!(registration.current.residue_name, "ILE");
+            assert_eq!(registration.current.sequence_number, 23);
+            assert_eq!(registration.previous.name, "O");
+            assert_eq!(registration.previous.residue_name, "THR");
+            assert_eq!(registration.previous.chain_id, 'A');
+            assert_eq!(registration.previous.sequence_number, 26);
+        } else {
+            panic!()
+        }
+    }
+}
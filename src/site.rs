@@ -0,0 +1,188 @@
+/*!
+Contains parsers related to [Site](http://www.wwpdb.org/documentation/file-format-content/format33/sect7.html#SITE) records.
+SITE records supply the identification of groups comprising important properties of the macromolecule.
+Residues of a site are listed over as many lines as needed, four residues per line.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{
+    character::complete::line_ending, do_parse, fold_many1, map, map_opt, named, opt, take,
+    take_str,
+};
+
+use crate::make_line_folder;
+use std::{marker::PhantomData, str};
+
+#[allow(dead_code)]
+struct SiteLine;
+
+/// site id of a line followed by columns 18 - 61 holding its four residue slots,
+/// padded to full width
+fn site_columns(site_id: &str, rest: &[u8]) -> String {
+    format!("{:<3.3}{:<44.44}", site_id, String::from_utf8_lossy(rest))
+}
+
+/// reads the residues of consecutive lines built by `site_columns`. Blank residue slots
+/// are skipped. Returns `None` if a line belongs to another site, a residue slot is
+/// malformed or the number of residues differs from `num_res`
+fn site_residues(lines: &[u8], site_id: &str, num_res: u32) -> Option<Vec<ResidueReference>> {
+    let mut residues = Vec::new();
+    for line in lines.chunks(47) {
+        if str::from_utf8(line.get(..3)?).ok()?.trim() != site_id {
+            return None;
+        }
+        for slot in line[3..].chunks(11) {
+            if slot.iter().all(|b| *b == b' ') {
+                continue;
+            }
+            residues.push(residue_reference(slot.get(1..)?).ok()?.1);
+        }
+    }
+    Some(residues).filter(|r| r.len() == num_res as usize)
+}
+
+named!(
+    site_line_parser<Continuation<SiteLine>>,
+    do_parse!(
+        site
+            >> take!(3)
+            >> continuation: map_opt!(threedigit_integer, |n: u32| Some(n).filter(|n| *n > 1))
+            >> take!(1)
+            >> site_id: take_str!(3)
+            >> take!(3)
+            >> remaining: till_line_ending
+            >> line_ending
+            >> (Continuation::<SiteLine> {
+                continuation,
+                remaining: site_columns(site_id.trim(), remaining),
+                phantom: PhantomData,
+            })
+    )
+);
+
+make_line_folder!(site_line_folder, site_line_parser, SiteLine, fixed_columns);
+
+named!(#[doc=r#"Parses SITE records of a single site together with its continuation lines.
+A following line numbered 1 starts the next site.
+If successfull returns [Record](../ast/types/enum.Record.html) variant containing [Site](../ast/types/struct.Site.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD       | DEFINITION                                         |
+|----------|--------------|-------------|----------------------------------------------------|
+| 1 -  6   | Record name  | "SITE  "    |                                                    |
+| 8 - 10   | Integer      | seqNum      | Sequence number.                                   |
+| 12 - 14  | LString(3)   | siteID      | Site name.                                         |
+| 16 - 17  | Integer      | numRes      | Number of residues that compose the site.          |
+| 19 - 21  | Residue name | resName1    | Residue name for first residue that                |
+|          |              |             | creates the site.                                  |
+| 23       | Character    | chainID1    | Chain identifier for first residue of site.        |
+| 24 - 27  | Integer      | seq1        | Residue sequence number for first residue          |
+|          |              |             | of the  site.                                      |
+| 28       | AChar        | iCode1      | Insertion code for first residue of the  site.     |
+| 30 - 32  | Residue name | resName2    | Residue name for second residue that               |
+|          |              |             | creates the site.                                  |
+| 34       | Character    | chainID2    | Chain identifier for second residue of             |
+|          |              |             | the  site.                                         |
+| 35 - 38  | Integer      | seq2        | Residue sequence number for second                 |
+|          |              |             | residue of the site.                               |
+| 39       | AChar        | iCode2      | Insertion code for second residue                  |
+|          |              |             | of the  site.                                      |
+| 41 - 43  | Residue name | resName3    | Residue name for third residue that                |
+|          |              |             | creates  the site.                                 |
+| 45       | Character    | chainID3    | Chain identifier for third residue                 |
+|          |              |             | of the site.                                       |
+| 46 - 49  | Integer      | seq3        | Residue sequence number for third                  |
+|          |              |             | residue of the site.                               |
+| 50       | AChar        | iCode3      | Insertion code for third residue                   |
+|          |              |             | of the site.                                       |
+| 52 - 54  | Residue name | resName4    | Residue name for fourth residue that               |
+|          |              |             | creates  the site.                                 |
+| 56       | Character    | chainID4    | Chain identifier for fourth residue                |
+|          |              |             | of the site.                                       |
+| 57 - 60  | Integer      | seq4        | Residue sequence number for fourth                 |
+|          |              |             | residue of the site.                               |
+| 61       | AChar        | iCode4      | Insertion code for fourth residue                  |
+|          |              |             | of the site.                                       |
+"#],
+    pub site_record_parser<Record>,
+    do_parse!(
+        site
+            >> take!(3)
+            >> threedigit_integer
+            >> take!(1)
+            >> site_id: map!(take_str!(3), str::trim)
+            >> take!(1)
+            >> num_res: twodigit_integer
+            >> first: till_line_ending
+            >> line_ending
+            >> site: map_opt!(opt!(site_line_folder), |rest: Option<Vec<u8>>| {
+                let lines = site_columns(site_id, first)
+                    + &String::from_utf8_lossy(&rest.unwrap_or_default());
+                Some(Site {
+                    site_id: site_id.to_owned(),
+                    num_res,
+                    residues: site_residues(lines.as_bytes(), site_id, num_res)?,
+                    source_lines: (lines.len() / 47) as u32,
+                })
+            })
+            >> (Record::Site(site))
+    )
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn site() {
+        let lines = r#"SITE     1 AC3  9 TYR A 175  SER A 178  LEU A 179  HOH A 633                    
+SITE     2 AC3  9 HOH A 664  HOH A 678  HOH A 859  HOH A 954                    
+SITE     3 AC3  9 HOH A 970
+SITE     1 AC4 21 GLU A  15  ASP A  33  GLY A  35  TYR A  75                    
+"#;
+        if let Ok((rest, Record::Site(res))) = site_record_parser(lines.as_bytes()) {
+            assert_eq!(res.site_id, "AC3");
+            assert_eq!(res.num_res, 9);
+            assert_eq!(res.residues.len(), 9);
+            assert_eq!(res.residues[0].residue_name, "TYR");
+            assert_eq!(res.residues[0].sequence_number, 175);
+            assert_eq!(res.residues[8].residue_name, "HOH");
+            assert_eq!(res.residues[8].chain_id, 'A');
+            assert_eq!(res.residues[8].sequence_number, 970);
+            assert_eq!(res.source_lines, 3);
+            assert!(rest.starts_with(b"SITE     1 AC4"));
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn site_insertion_codes() {
+        let lines = "SITE     1 AC1  5 TYR A 175A SER A 178  LEU A 179  HOH A 633B\n\
+                     SITE     2 AC1  5 HOH A 664\n";
+        if let Ok((rest, Record::Site(res))) = site_record_parser(lines.as_bytes()) {
+            assert!(rest.is_empty());
+            assert_eq!(res.residues.len(), 5);
+            assert_eq!(res.residues[0].insertion_code, Some('A'));
+            assert_eq!(res.residues[3].insertion_code, Some('B'));
+            assert_eq!(res.residues[4].sequence_number, 664);
+            assert_eq!(res.residues[4].insertion_code, None);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn site_malformed() {
+        let malformed_residue = r#"SITE     1 AC3  4 TYR A 175  SER A 17x  LEU A 179  HOH A 633
+"#;
+        assert!(site_record_parser(malformed_residue.as_bytes()).is_err());
+        let wrong_count = r#"SITE     1 AC3  5 TYR A 175  SER A 178  LEU A 179  HOH A 633
+"#;
+        assert!(site_record_parser(wrong_count.as_bytes()).is_err());
+        let other_site = r#"SITE     1 AC3  5 TYR A 175  SER A 178  LEU A 179  HOH A 633
+SITE     2 AC4  5 HOH A 664
+"#;
+        assert!(site_record_parser(other_site.as_bytes()).is_err());
+    }
+}
@@ -25,7 +25,7 @@ named!(
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<SprsdeLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
+                continuation: cont.unwrap_or_default(),
                 remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
                 phantom: PhantomData,
             })
@@ -82,7 +82,7 @@ Record layout :
             res
         } else {
             Record::Sprsde(Sprsde {
-                sprsde_date: chrono::naive::MIN_DATE,
+                sprsde_date: chrono::NaiveDate::MIN,
                 id_code: String::new(),
                 superseeded: Vec::new(),
             })
@@ -0,0 +1,98 @@
+/*!
+Contains parsers related to [Ssbond](http://www.wwpdb.org/documentation/file-format-content/format33/sect6.html#SSBOND) records.
+The SSBOND record identifies each disulfide bond in protein and polypeptide structures by identifying the two
+residues involved in the bond.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{character::complete::line_ending, do_parse, map_res, named, take, value};
+
+named!(
+    #[doc=r#"Parses SSBOND record. If successfull returns [Record](../ast/types/enum.Record.html) variant
+containing [Ssbond](../ast/types/struct.Ssbond.html) instance. Symmetry operators and bond length are
+`None` when their columns are blank, a value that does not parse fails the record.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD       | DEFINITION                                         |
+|----------|--------------|-------------|----------------------------------------------------|
+| 1 -  6   | Record name  | "SSBOND"    |                                                    |
+| 8 - 10   | Integer      | serNum      | Serial number.                                     |
+| 12 - 14  | LString(3)   | "CYS"       | Residue name.                                      |
+| 16       | Character    | chainID1    | Chain identifier.                                  |
+| 18 - 21  | Integer      | seqNum1     | Residue sequence number.                           |
+| 22       | AChar        | icode1      | Insertion code.                                    |
+| 26 - 28  | LString(3)   | "CYS"       | Residue name.                                      |
+| 30       | Character    | chainID2    | Chain identifier.                                  |
+| 32 - 35  | Integer      | seqNum2     | Residue sequence number.                           |
+| 36       | AChar        | icode2      | Insertion code.                                    |
+| 60 - 65  | SymOP        | sym1        | Symmetry operator for residue 1.                   |
+| 67 - 72  | SymOP        | sym2        | Symmetry operator for residue 2.                   |
+| 74 – 78  | Real(5.2)    | Length      | Disulfide bond distance                            |
+"#],
+    pub ssbond_record_parser<Record>,
+    do_parse!(
+        ssbond
+            >> take!(1)
+            >> serial: threedigit_integer
+            >> take!(1)
+            >> residue1: spaced_residue_reference
+            >> take!(3)
+            >> residue2: spaced_residue_reference
+            >> rest: till_line_ending
+            >> symmetry1: map_res!(value!(rest), |r| parsed_column(r, 23, 29))
+            >> symmetry2: map_res!(value!(rest), |r| parsed_column(r, 30, 36))
+            >> length: map_res!(value!(rest), |r| parsed_column(r, 37, 42))
+            >> line_ending
+            >> (Record::Ssbond(Ssbond {
+                serial,
+                residue1,
+                residue2,
+                symmetry1,
+                symmetry2,
+                length,
+            }))
+    )
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ssbond() {
+        if let Ok((_, Record::Ssbond(res))) = ssbond_record_parser(
+            "SSBOND   1 CYS A  249    CYS A  283                          1555   1555  2.07  \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.serial, 1);
+            assert_eq!(res.residue1.residue_name, "CYS");
+            assert_eq!(res.residue1.chain_id, 'A');
+            assert_eq!(res.residue1.sequence_number, 249);
+            assert_eq!(res.residue2.sequence_number, 283);
+            assert_eq!(
+                res.symmetry1,
+                Some(SymmetryOperator {
+                    operator: 1,
+                    translation: [0, 0, 0]
+                })
+            );
+            assert_eq!(res.length, Some(2.07));
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn ssbond_malformed_columns() {
+        assert!(ssbond_record_parser(
+            "SSBOND   1 CYS A  249    CYS A  283                          1555   1555  2.x7  \n"
+                .as_bytes(),
+        )
+        .is_err());
+        assert!(ssbond_record_parser(
+            "SSBOND   1 CYS A  249    CYS A  283                          1x55   1555  2.07  \n"
+                .as_bytes(),
+        )
+        .is_err());
+    }
+}
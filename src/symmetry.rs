@@ -0,0 +1,721 @@
+/*!
+Space group symmetry. Contains the table of the 230 crystallographic space groups
+keyed by the Hermann-Mauguin symbols written in
+[CRYST1](http://www.wwpdb.org/documentation/file-format-content/format33/sect8.html#CRYST1)
+records. Rhombohedral groups are listed both in the hexagonal (`H`) and the
+rhombohedral (`R`) setting.
+
+Symmetry operators are generated from the
+[Hall symbol](http://cci.lbl.gov/sginfo/hall_symbols.html) of each group and
+are given in fractional coordinates.
+*/
+use super::ast::types::Matrix3x4;
+use std::collections::HashSet;
+
+/// a crystallographic space group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpaceGroup {
+    /// number in International Tables for Crystallography
+    pub number: u32,
+    /// full Hermann-Mauguin symbol as written in CRYST1 records
+    pub symbol: &'static str,
+    hall: &'static str,
+}
+
+macro_rules! space_group {
+    ($number : expr, $symbol : expr, $hall : expr) => {
+        SpaceGroup {
+            number: $number,
+            symbol: $symbol,
+            hall: $hall,
+        }
+    };
+}
+
+/// all space groups in the order of their numbers
+pub static SPACE_GROUPS: &[SpaceGroup] = &[
+    space_group!(1, "P 1", "P 1"),
+    space_group!(2, "P -1", "-P 1"),
+    space_group!(3, "P 1 2 1", "P 2y"),
+    space_group!(4, "P 1 21 1", "P 2yb"),
+    space_group!(5, "C 1 2 1", "C 2y"),
+    space_group!(6, "P 1 m 1", "P -2y"),
+    space_group!(7, "P 1 c 1", "P -2yc"),
+    space_group!(8, "C 1 m 1", "C -2y"),
+    space_group!(9, "C 1 c 1", "C -2yc"),
+    space_group!(10, "P 1 2/m 1", "-P 2y"),
+    space_group!(11, "P 1 21/m 1", "-P 2yb"),
+    space_group!(12, "C 1 2/m 1", "-C 2y"),
+    space_group!(13, "P 1 2/c 1", "-P 2yc"),
+    space_group!(14, "P 1 21/c 1", "-P 2ybc"),
+    space_group!(15, "C 1 2/c 1", "-C 2yc"),
+    space_group!(16, "P 2 2 2", "P 2 2"),
+    space_group!(17, "P 2 2 21", "P 2c 2"),
+    space_group!(18, "P 21 21 2", "P 2 2ab"),
+    space_group!(19, "P 21 21 21", "P 2ac 2ab"),
+    space_group!(20, "C 2 2 21", "C 2c 2"),
+    space_group!(21, "C 2 2 2", "C 2 2"),
+    space_group!(22, "F 2 2 2", "F 2 2"),
+    space_group!(23, "I 2 2 2", "I 2 2"),
+    space_group!(24, "I 21 21 21", "I 2b 2c"),
+    space_group!(25, "P m m 2", "P 2 -2"),
+    space_group!(26, "P m c 21", "P 2c -2"),
+    space_group!(27, "P c c 2", "P 2 -2c"),
+    space_group!(28, "P m a 2", "P 2 -2a"),
+    space_group!(29, "P c a 21", "P 2c -2ac"),
+    space_group!(30, "P n c 2", "P 2 -2bc"),
+    space_group!(31, "P m n 21", "P 2ac -2"),
+    space_group!(32, "P b a 2", "P 2 -2ab"),
+    space_group!(33, "P n a 21", "P 2c -2n"),
+    space_group!(34, "P n n 2", "P 2 -2n"),
+    space_group!(35, "C m m 2", "C 2 -2"),
+    space_group!(36, "C m c 21", "C 2c -2"),
+    space_group!(37, "C c c 2", "C 2 -2c"),
+    space_group!(38, "A m m 2", "A 2 -2"),
+    space_group!(39, "A b m 2", "A 2 -2c"),
+    space_group!(40, "A m a 2", "A 2 -2a"),
+    space_group!(41, "A b a 2", "A 2 -2ac"),
+    space_group!(42, "F m m 2", "F 2 -2"),
+    space_group!(43, "F d d 2", "F 2 -2d"),
+    space_group!(44, "I m m 2", "I 2 -2"),
+    space_group!(45, "I b a 2", "I 2 -2c"),
+    space_group!(46, "I m a 2", "I 2 -2a"),
+    space_group!(47, "P m m m", "-P 2 2"),
+    space_group!(48, "P n n n", "P 2 2 -1n"),
+    space_group!(49, "P c c m", "-P 2 2c"),
+    space_group!(50, "P b a n", "P 2 2 -1ab"),
+    space_group!(51, "P m m a", "-P 2a 2a"),
+    space_group!(52, "P n n a", "-P 2a 2bc"),
+    space_group!(53, "P m n a", "-P 2ac 2"),
+    space_group!(54, "P c c a", "-P 2a 2ac"),
+    space_group!(55, "P b a m", "-P 2 2ab"),
+    space_group!(56, "P c c n", "-P 2ab 2ac"),
+    space_group!(57, "P b c m", "-P 2c 2b"),
+    space_group!(58, "P n n m", "-P 2 2n"),
+    space_group!(59, "P m m n", "P 2 2ab -1ab"),
+    space_group!(60, "P b c n", "-P 2n 2ab"),
+    space_group!(61, "P b c a", "-P 2ac 2ab"),
+    space_group!(62, "P n m a", "-P 2ac 2n"),
+    space_group!(63, "C m c m", "-C 2c 2"),
+    space_group!(64, "C m c a", "-C 2ac 2"),
+    space_group!(65, "C m m m", "-C 2 2"),
+    space_group!(66, "C c c m", "-C 2 2c"),
+    space_group!(67, "C m m a", "-C 2a 2"),
+    space_group!(68, "C c c a", "C 2 2 -1ac"),
+    space_group!(69, "F m m m", "-F 2 2"),
+    space_group!(70, "F d d d", "F 2 2 -1d"),
+    space_group!(71, "I m m m", "-I 2 2"),
+    space_group!(72, "I b a m", "-I 2 2c"),
+    space_group!(73, "I b c a", "-I 2b 2c"),
+    space_group!(74, "I m m a", "-I 2b 2"),
+    space_group!(75, "P 4", "P 4"),
+    space_group!(76, "P 41", "P 4w"),
+    space_group!(77, "P 42", "P 4c"),
+    space_group!(78, "P 43", "P 4cw"),
+    space_group!(79, "I 4", "I 4"),
+    space_group!(80, "I 41", "I 4bw"),
+    space_group!(81, "P -4", "P -4"),
+    space_group!(82, "I -4", "I -4"),
+    space_group!(83, "P 4/m", "-P 4"),
+    space_group!(84, "P 42/m", "-P 4c"),
+    space_group!(85, "P 4/n", "P 4ab -1ab"),
+    space_group!(86, "P 42/n", "P 4n -1n"),
+    space_group!(87, "I 4/m", "-I 4"),
+    space_group!(88, "I 41/a", "I 4bw -1bw"),
+    space_group!(89, "P 4 2 2", "P 4 2"),
+    space_group!(90, "P 4 21 2", "P 4ab 2ab"),
+    space_group!(91, "P 41 2 2", "P 4w 2c"),
+    space_group!(92, "P 41 21 2", "P 4abw 2nw"),
+    space_group!(93, "P 42 2 2", "P 4c 2"),
+    space_group!(94, "P 42 21 2", "P 4n 2n"),
+    space_group!(95, "P 43 2 2", "P 4cw 2c"),
+    space_group!(96, "P 43 21 2", "P 4nw 2abw"),
+    space_group!(97, "I 4 2 2", "I 4 2"),
+    space_group!(98, "I 41 2 2", "I 4bw 2bw"),
+    space_group!(99, "P 4 m m", "P 4 -2"),
+    space_group!(100, "P 4 b m", "P 4 -2ab"),
+    space_group!(101, "P 42 c m", "P 4c -2c"),
+    space_group!(102, "P 42 n m", "P 4n -2n"),
+    space_group!(103, "P 4 c c", "P 4 -2c"),
+    space_group!(104, "P 4 n c", "P 4 -2n"),
+    space_group!(105, "P 42 m c", "P 4c -2"),
+    space_group!(106, "P 42 b c", "P 4c -2ab"),
+    space_group!(107, "I 4 m m", "I 4 -2"),
+    space_group!(108, "I 4 c m", "I 4 -2c"),
+    space_group!(109, "I 41 m d", "I 4bw -2"),
+    space_group!(110, "I 41 c d", "I 4bw -2c"),
+    space_group!(111, "P -4 2 m", "P -4 2"),
+    space_group!(112, "P -4 2 c", "P -4 2c"),
+    space_group!(113, "P -4 21 m", "P -4 2ab"),
+    space_group!(114, "P -4 21 c", "P -4 2n"),
+    space_group!(115, "P -4 m 2", "P -4 -2"),
+    space_group!(116, "P -4 c 2", "P -4 -2c"),
+    space_group!(117, "P -4 b 2", "P -4 -2ab"),
+    space_group!(118, "P -4 n 2", "P -4 -2n"),
+    space_group!(119, "I -4 m 2", "I -4 -2"),
+    space_group!(120, "I -4 c 2", "I -4 -2c"),
+    space_group!(121, "I -4 2 m", "I -4 2"),
+    space_group!(122, "I -4 2 d", "I -4 2bw"),
+    space_group!(123, "P 4/m m m", "-P 4 2"),
+    space_group!(124, "P 4/m c c", "-P 4 2c"),
+    space_group!(125, "P 4/n b m", "P 4 2 -1ab"),
+    space_group!(126, "P 4/n n c", "P 4 2 -1n"),
+    space_group!(127, "P 4/m b m", "-P 4 2ab"),
+    space_group!(128, "P 4/m n c", "-P 4 2n"),
+    space_group!(129, "P 4/n m m", "P 4ab 2ab -1ab"),
+    space_group!(130, "P 4/n c c", "P 4ab 2n -1ab"),
+    space_group!(131, "P 42/m m c", "-P 4c 2"),
+    space_group!(132, "P 42/m c m", "-P 4c 2c"),
+    space_group!(133, "P 42/n b c", "P 4n 2c -1n"),
+    space_group!(134, "P 42/n n m", "P 4n 2 -1n"),
+    space_group!(135, "P 42/m b c", "-P 4c 2ab"),
+    space_group!(136, "P 42/m n m", "-P 4n 2n"),
+    space_group!(137, "P 42/n m c", "P 4n 2n -1n"),
+    space_group!(138, "P 42/n c m", "P 4n 2ab -1n"),
+    space_group!(139, "I 4/m m m", "-I 4 2"),
+    space_group!(140, "I 4/m c m", "-I 4 2c"),
+    space_group!(141, "I 41/a m d", "I 4bw 2bw -1bw"),
+    space_group!(142, "I 41/a c d", "I 4bw 2aw -1bw"),
+    space_group!(143, "P 3", "P 3"),
+    space_group!(144, "P 31", "P 31"),
+    space_group!(145, "P 32", "P 32"),
+    space_group!(146, "H 3", "R 3"),
+    space_group!(146, "R 3", "P 3*"),
+    space_group!(147, "P -3", "-P 3"),
+    space_group!(148, "H -3", "-R 3"),
+    space_group!(148, "R -3", "-P 3*"),
+    space_group!(149, "P 3 1 2", "P 3 2"),
+    space_group!(150, "P 3 2 1", "P 3 2\""),
+    space_group!(151, "P 31 1 2", "P 31 2c (0 0 1)"),
+    space_group!(152, "P 31 2 1", "P 31 2\""),
+    space_group!(153, "P 32 1 2", "P 32 2c (0 0 -1)"),
+    space_group!(154, "P 32 2 1", "P 32 2\""),
+    space_group!(155, "H 3 2", "R 3 2\""),
+    space_group!(155, "R 3 2", "P 3* 2"),
+    space_group!(156, "P 3 m 1", "P 3 -2\""),
+    space_group!(157, "P 3 1 m", "P 3 -2"),
+    space_group!(158, "P 3 c 1", "P 3 -2\"c"),
+    space_group!(159, "P 3 1 c", "P 3 -2c"),
+    space_group!(160, "H 3 m", "R 3 -2\""),
+    space_group!(160, "R 3 m", "P 3* -2"),
+    space_group!(161, "H 3 c", "R 3 -2\"c"),
+    space_group!(161, "R 3 c", "P 3* -2n"),
+    space_group!(162, "P -3 1 m", "-P 3 2"),
+    space_group!(163, "P -3 1 c", "-P 3 2c"),
+    space_group!(164, "P -3 m 1", "-P 3 2\""),
+    space_group!(165, "P -3 c 1", "-P 3 2\"c"),
+    space_group!(166, "H -3 m", "-R 3 2\""),
+    space_group!(166, "R -3 m", "-P 3* 2"),
+    space_group!(167, "H -3 c", "-R 3 2\"c"),
+    space_group!(167, "R -3 c", "-P 3* 2n"),
+    space_group!(168, "P 6", "P 6"),
+    space_group!(169, "P 61", "P 61"),
+    space_group!(170, "P 65", "P 65"),
+    space_group!(171, "P 62", "P 62"),
+    space_group!(172, "P 64", "P 64"),
+    space_group!(173, "P 63", "P 6c"),
+    space_group!(174, "P -6", "P -6"),
+    space_group!(175, "P 6/m", "-P 6"),
+    space_group!(176, "P 63/m", "-P 6c"),
+    space_group!(177, "P 6 2 2", "P 6 2"),
+    space_group!(178, "P 61 2 2", "P 61 2 (0 0 -1)"),
+    space_group!(179, "P 65 2 2", "P 65 2 (0 0 1)"),
+    space_group!(180, "P 62 2 2", "P 62 2c (0 0 1)"),
+    space_group!(181, "P 64 2 2", "P 64 2c (0 0 -1)"),
+    space_group!(182, "P 63 2 2", "P 6c 2c"),
+    space_group!(183, "P 6 m m", "P 6 -2"),
+    space_group!(184, "P 6 c c", "P 6 -2c"),
+    space_group!(185, "P 63 c m", "P 6c -2"),
+    space_group!(186, "P 63 m c", "P 6c -2c"),
+    space_group!(187, "P -6 m 2", "P -6 2"),
+    space_group!(188, "P -6 c 2", "P -6c 2"),
+    space_group!(189, "P -6 2 m", "P -6 -2"),
+    space_group!(190, "P -6 2 c", "P -6c -2c"),
+    space_group!(191, "P 6/m m m", "-P 6 2"),
+    space_group!(192, "P 6/m c c", "-P 6 2c"),
+    space_group!(193, "P 63/m c m", "-P 6c 2"),
+    space_group!(194, "P 63/m m c", "-P 6c 2c"),
+    space_group!(195, "P 2 3", "P 2 2 3"),
+    space_group!(196, "F 2 3", "F 2 2 3"),
+    space_group!(197, "I 2 3", "I 2 2 3"),
+    space_group!(198, "P 21 3", "P 2ac 2ab 3"),
+    space_group!(199, "I 21 3", "I 2b 2c 3"),
+    space_group!(200, "P m -3", "-P 2 2 3"),
+    space_group!(201, "P n -3", "P 2 2 3 -1n"),
+    space_group!(202, "F m -3", "-F 2 2 3"),
+    space_group!(203, "F d -3", "F 2 2 3 -1d"),
+    space_group!(204, "I m -3", "-I 2 2 3"),
+    space_group!(205, "P a -3", "-P 2ac 2ab 3"),
+    space_group!(206, "I a -3", "-I 2b 2c 3"),
+    space_group!(207, "P 4 3 2", "P 4 2 3"),
+    space_group!(208, "P 42 3 2", "P 4n 2 3"),
+    space_group!(209, "F 4 3 2", "F 4 2 3"),
+    space_group!(210, "F 41 3 2", "F 4d 2 3"),
+    space_group!(211, "I 4 3 2", "I 4 2 3"),
+    space_group!(212, "P 43 3 2", "P 4acd 2ab 3"),
+    space_group!(213, "P 41 3 2", "P 4bd 2ab 3"),
+    space_group!(214, "I 41 3 2", "I 4bd 2c 3"),
+    space_group!(215, "P -4 3 m", "P -4 2 3"),
+    space_group!(216, "F -4 3 m", "F -4 2 3"),
+    space_group!(217, "I -4 3 m", "I -4 2 3"),
+    space_group!(218, "P -4 3 n", "P -4n 2 3"),
+    space_group!(219, "F -4 3 c", "F -4c 2 3"),
+    space_group!(220, "I -4 3 d", "I -4bd 2c 3"),
+    space_group!(221, "P m -3 m", "-P 4 2 3"),
+    space_group!(222, "P n -3 n", "P 4 2 3 -1n"),
+    space_group!(223, "P m -3 n", "-P 4n 2 3"),
+    space_group!(224, "P n -3 m", "P 4n 2 3 -1n"),
+    space_group!(225, "F m -3 m", "-F 4 2 3"),
+    space_group!(226, "F m -3 c", "-F 4c 2 3"),
+    space_group!(227, "F d -3 m", "F 4d 2 3 -1d"),
+    space_group!(228, "F d -3 c", "F 4d 2 3 -1cd"),
+    space_group!(229, "I m -3 m", "-I 4 2 3"),
+    space_group!(230, "I a -3 d", "-I 4bd 2c 3"),
+];
+
+/// translations are kept as integer multiples of 1/24
+const DENOMINATOR: i32 = 24;
+
+type Rotation = [[i32; 3]; 3];
+
+const IDENTITY: Rotation = [[1, 0, 0], [0, 1, 0], [0, 0, 1]];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Operation {
+    rotation: Rotation,
+    translation: [i32; 3],
+}
+
+impl Operation {
+    fn new(rotation: Rotation, translation: [i32; 3]) -> Self {
+        Operation {
+            rotation,
+            translation: [
+                translation[0].rem_euclid(DENOMINATOR),
+                translation[1].rem_euclid(DENOMINATOR),
+                translation[2].rem_euclid(DENOMINATOR),
+            ],
+        }
+    }
+
+    fn rotate(&self, vector: [i32; 3]) -> [i32; 3] {
+        let row = |r: &[i32; 3]| r[0] * vector[0] + r[1] * vector[1] + r[2] * vector[2];
+        [
+            row(&self.rotation[0]),
+            row(&self.rotation[1]),
+            row(&self.rotation[2]),
+        ]
+    }
+
+    /// operation applying `other` first and `self` afterwards
+    fn compose(&self, other: &Operation) -> Operation {
+        let mut rotation = [[0; 3]; 3];
+        for (r, row) in rotation.iter_mut().enumerate() {
+            for (c, value) in row.iter_mut().enumerate() {
+                *value = (0..3)
+                    .map(|k| self.rotation[r][k] * other.rotation[k][c])
+                    .sum();
+            }
+        }
+        let rotated = self.rotate(other.translation);
+        Operation::new(
+            rotation,
+            [
+                rotated[0] + self.translation[0],
+                rotated[1] + self.translation[1],
+                rotated[2] + self.translation[2],
+            ],
+        )
+    }
+
+    /// same operation with the origin moved by `shift`
+    fn shifted(&self, shift: [i32; 3]) -> Operation {
+        let rotated = self.rotate(shift);
+        Operation::new(
+            self.rotation,
+            [
+                self.translation[0] + shift[0] - rotated[0],
+                self.translation[1] + shift[1] - rotated[1],
+                self.translation[2] + shift[2] - rotated[2],
+            ],
+        )
+    }
+
+    fn to_matrix(self) -> Matrix3x4 {
+        let mut matrix = Matrix3x4::identity();
+        for (r, row) in matrix.rows.iter_mut().enumerate() {
+            for (c, value) in row.iter_mut().take(3).enumerate() {
+                *value = f64::from(self.rotation[r][c]);
+            }
+            row[3] = f64::from(self.translation[r]) / f64::from(DENOMINATOR);
+        }
+        matrix
+    }
+}
+
+fn negated(rotation: Rotation) -> Rotation {
+    let mut negated = rotation;
+    for value in negated.iter_mut().flatten() {
+        *value = -*value;
+    }
+    negated
+}
+
+fn rotation(axis: char, order: i32) -> Option<Rotation> {
+    let rotation = match (axis, order) {
+        (_, 1) => IDENTITY,
+        ('x', 2) => [[1, 0, 0], [0, -1, 0], [0, 0, -1]],
+        ('x', 3) => [[1, 0, 0], [0, 0, -1], [0, 1, -1]],
+        ('x', 4) => [[1, 0, 0], [0, 0, -1], [0, 1, 0]],
+        ('x', 6) => [[1, 0, 0], [0, 1, -1], [0, 1, 0]],
+        ('y', 2) => [[-1, 0, 0], [0, 1, 0], [0, 0, -1]],
+        ('y', 3) => [[-1, 0, 1], [0, 1, 0], [-1, 0, 0]],
+        ('y', 4) => [[0, 0, 1], [0, 1, 0], [-1, 0, 0]],
+        ('y', 6) => [[0, 0, 1], [0, 1, 0], [-1, 0, 1]],
+        ('z', 2) => [[-1, 0, 0], [0, -1, 0], [0, 0, 1]],
+        ('z', 3) => [[0, -1, 0], [1, -1, 0], [0, 0, 1]],
+        ('z', 4) => [[0, -1, 0], [1, 0, 0], [0, 0, 1]],
+        ('z', 6) => [[1, -1, 0], [1, 0, 0], [0, 0, 1]],
+        ('\'', 2) => [[0, -1, 0], [-1, 0, 0], [0, 0, -1]],
+        ('"', 2) => [[0, 1, 0], [1, 0, 0], [0, 0, -1]],
+        ('*', 3) => [[0, 0, 1], [1, 0, 0], [0, 1, 0]],
+        _ => return None,
+    };
+    Some(rotation)
+}
+
+fn axis_direction(axis: char) -> [i32; 3] {
+    match axis {
+        'x' => [1, 0, 0],
+        'y' => [0, 1, 0],
+        _ => [0, 0, 1],
+    }
+}
+
+fn translation(symbol: char) -> Option<[i32; 3]> {
+    let half = DENOMINATOR / 2;
+    let quarter = DENOMINATOR / 4;
+    let translation = match symbol {
+        'a' => [half, 0, 0],
+        'b' => [0, half, 0],
+        'c' => [0, 0, half],
+        'n' => [half, half, half],
+        'u' => [quarter, 0, 0],
+        'v' => [0, quarter, 0],
+        'w' => [0, 0, quarter],
+        'd' => [quarter, quarter, quarter],
+        _ => return None,
+    };
+    Some(translation)
+}
+
+fn centering(lattice: &str) -> Option<&'static [[i32; 3]]> {
+    let vectors: &'static [[i32; 3]] = match lattice {
+        "P" => &[],
+        "A" => &[[0, 12, 12]],
+        "B" => &[[12, 0, 12]],
+        "C" => &[[12, 12, 0]],
+        "I" => &[[12, 12, 12]],
+        "R" => &[[16, 8, 8], [8, 16, 16]],
+        "F" => &[[0, 12, 12], [12, 0, 12], [12, 12, 0]],
+        _ => return None,
+    };
+    Some(vectors)
+}
+
+/// generator given by a single matrix symbol of a Hall symbol, e.g. `-2xc` or `31`
+fn hall_generator(symbol: &str, index: usize, previous_order: i32) -> Option<Operation> {
+    let improper = symbol.starts_with('-');
+    let mut chars = symbol.trim_start_matches('-').chars().peekable();
+    let order = chars.next()?.to_digit(10)? as i32;
+    let screw = match chars.peek().and_then(|c| c.to_digit(10)) {
+        Some(screw) => {
+            chars.next();
+            screw as i32
+        }
+        None => 0,
+    };
+    let mut axis = None;
+    let mut shift = [0; 3];
+    for c in chars {
+        match c {
+            'x' | 'y' | 'z' | '\'' | '"' | '*' => axis = Some(c),
+            _ => {
+                let t = translation(c)?;
+                for (s, t) in shift.iter_mut().zip(t.iter()) {
+                    *s += t;
+                }
+            }
+        }
+    }
+    let axis = match (axis, index, order) {
+        (Some(axis), _, _) => axis,
+        (None, _, 1) | (None, 0, _) => 'z',
+        (None, 1, 2) if previous_order == 2 || previous_order == 4 => 'x',
+        (None, 1, 2) => '\'',
+        (None, 2, 3) => '*',
+        _ => return None,
+    };
+    let mut rotation = rotation(axis, order)?;
+    if screw > 0 {
+        let direction = axis_direction(axis);
+        for (s, d) in shift.iter_mut().zip(direction.iter()) {
+            *s += DENOMINATOR * screw / order * d;
+        }
+    }
+    if improper {
+        rotation = negated(rotation);
+    }
+    Some(Operation::new(rotation, shift))
+}
+
+/// all operations of the group given by a Hall symbol, starting with the identity
+fn hall_operations(hall: &str) -> Option<Vec<Operation>> {
+    let (symbols, origin) = match hall.find('(') {
+        Some(position) => (&hall[..position], Some(&hall[position + 1..])),
+        None => (hall, None),
+    };
+    let mut tokens = symbols.split_whitespace();
+    let lattice = tokens.next()?;
+    let mut generators = Vec::new();
+    let mut previous_order = 0;
+    for (index, symbol) in tokens.enumerate() {
+        let generator = hall_generator(symbol, index, previous_order)?;
+        previous_order = symbol
+            .trim_start_matches('-')
+            .chars()
+            .next()?
+            .to_digit(10)? as i32;
+        generators.push(generator);
+    }
+    if lattice.starts_with('-') {
+        generators.push(Operation::new(negated(IDENTITY), [0; 3]));
+    }
+    for vector in centering(lattice.trim_start_matches('-'))? {
+        generators.push(Operation::new(IDENTITY, *vector));
+    }
+
+    let identity = Operation::new(IDENTITY, [0; 3]);
+    let mut operations = vec![identity];
+    let mut seen: HashSet<Operation> = operations.iter().cloned().collect();
+    let mut index = 0;
+    while index < operations.len() {
+        let operation = operations[index];
+        for generator in generators.iter() {
+            let product = generator.compose(&operation);
+            if seen.insert(product) {
+                operations.push(product);
+            }
+        }
+        index += 1;
+    }
+
+    if let Some(origin) = origin {
+        // origin shift is given in twelfths of the unit cell
+        let mut shift = [0; 3];
+        let mut values = origin.trim_end_matches(')').split_whitespace();
+        for s in shift.iter_mut() {
+            *s = values.next()?.parse::<i32>().ok()? * DENOMINATOR / 12;
+        }
+        operations = operations.iter().map(|o| o.shifted(shift)).collect();
+    }
+    Some(operations)
+}
+
+fn normalized_symbol(symbol: &str) -> String {
+    symbol
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_uppercase()
+}
+
+/// short monoclinic symbol, e.g. `P 21` for `P 1 21 1`
+fn short_symbol(symbol: &str) -> Option<String> {
+    let tokens = symbol.split_whitespace().collect::<Vec<_>>();
+    if tokens.len() != 4 {
+        return None;
+    }
+    let axes = tokens[1..]
+        .iter()
+        .filter(|t| **t != "1")
+        .collect::<Vec<_>>();
+    match axes.as_slice() {
+        [axis] => Some(format!("{} {}", tokens[0], axis)),
+        _ => None,
+    }
+}
+
+impl SpaceGroup {
+    /// looks up a space group by its Hermann-Mauguin symbol. Whitespace and case
+    /// are ignored. Monoclinic groups are also found by their short symbols,
+    /// e.g. `P 21` for `P 1 21 1`
+    pub fn from_symbol(symbol: &str) -> Option<SpaceGroup> {
+        let symbol = normalized_symbol(symbol);
+        SPACE_GROUPS
+            .iter()
+            .find(|g| g.symbol == symbol)
+            .or_else(|| {
+                SPACE_GROUPS.iter().find(|g| {
+                    (3..=15).contains(&g.number)
+                        && short_symbol(g.symbol).as_deref() == Some(symbol.as_str())
+                })
+            })
+            .copied()
+    }
+
+    /// looks up a space group by its number. Rhombohedral groups are returned in
+    /// the hexagonal setting
+    pub fn from_number(number: u32) -> Option<SpaceGroup> {
+        SPACE_GROUPS.iter().find(|g| g.number == number).copied()
+    }
+
+    /// Hall symbol the symmetry operators are generated from
+    pub fn hall_symbol(&self) -> &'static str {
+        self.hall
+    }
+
+    /// symmetry operators in fractional coordinates. The first one is the identity,
+    /// translations are reduced to the range [0, 1)
+    pub fn operators(&self) -> Vec<Matrix3x4> {
+        hall_operations(self.hall)
+            .unwrap_or_default()
+            .iter()
+            .map(|o| o.to_matrix())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::record::test::{get_test_file_path, read_file};
+
+    fn point_group_order(number: u32) -> usize {
+        match number {
+            1 => 1,
+            2..=9 => 2,
+            10..=46 => 4,
+            47..=74 => 8,
+            75..=82 => 4,
+            83..=122 => 8,
+            123..=142 => 16,
+            143..=146 => 3,
+            147..=161 => 6,
+            162..=167 => 12,
+            168..=174 => 6,
+            175..=190 => 12,
+            191..=194 => 24,
+            195..=199 => 12,
+            200..=220 => 24,
+            _ => 48,
+        }
+    }
+
+    fn centering_multiplicity(symbol: &str) -> usize {
+        match &symbol[..1] {
+            "A" | "B" | "C" | "I" => 2,
+            "H" => 3,
+            "F" => 4,
+            _ => 1,
+        }
+    }
+
+    #[test]
+    fn table_is_complete() {
+        for number in 1..=230 {
+            assert!(SpaceGroup::from_number(number).is_some(), "{}", number);
+        }
+        assert_eq!(SPACE_GROUPS.len(), 237);
+    }
+
+    #[test]
+    fn operator_counts() {
+        for group in SPACE_GROUPS {
+            let operators = hall_operations(group.hall).unwrap();
+            assert_eq!(
+                operators.len(),
+                point_group_order(group.number) * centering_multiplicity(group.symbol),
+                "{} {}",
+                group.number,
+                group.symbol
+            );
+            assert_eq!(operators[0], Operation::new(IDENTITY, [0; 3]));
+        }
+    }
+
+    #[test]
+    fn symbol_lookup() {
+        assert_eq!(SpaceGroup::from_symbol("P 21 21 21").unwrap().number, 19);
+        assert_eq!(SpaceGroup::from_symbol(" p 21  21 21 ").unwrap().number, 19);
+        assert_eq!(SpaceGroup::from_symbol("C 1 2 1").unwrap().number, 5);
+        assert_eq!(SpaceGroup::from_symbol("P 21").unwrap().number, 4);
+        assert_eq!(SpaceGroup::from_symbol("H 3 2").unwrap().number, 155);
+        assert_eq!(
+            SpaceGroup::from_symbol("R 3 2").unwrap().hall_symbol(),
+            "P 3* 2"
+        );
+        assert_eq!(SpaceGroup::from_symbol("P 43 21 2").unwrap().number, 96);
+        assert!(SpaceGroup::from_symbol("X 1").is_none());
+    }
+
+    #[test]
+    fn p212121_operators() {
+        let operators = SpaceGroup::from_number(19).unwrap().operators();
+        let apply = |point| operators.iter().map(|o| o.apply(point)).collect::<Vec<_>>();
+        let images = apply([0.1, 0.2, 0.3]);
+        for expected in &[
+            [0.1, 0.2, 0.3],
+            [0.4, 0.8, 0.8],
+            [0.9, 0.7, 0.2],
+            [0.6, 0.3, 0.7],
+        ] {
+            assert!(
+                images.iter().any(
+                    |i| i
+                        .iter()
+                        .zip(expected.iter())
+                        .all(|(a, b)| ((a - b + 0.5).rem_euclid(1.0) - 0.5).abs() < 1e-9)
+                ),
+                "{:?}",
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn crystal_symmetry() {
+        let contents = read_file(&get_test_file_path("1BXO.pdb"))
+            .lines()
+            .filter(|l| {
+                ["CRYST1", "SCALE", "ATOM", "HETATM"]
+                    .iter()
+                    .any(|r| l.starts_with(r))
+            })
+            .map(|l| format!("{}\n", l))
+            .collect::<String>();
+        let (_, pdb) = crate::parse(&contents).unwrap();
+        let crystal = pdb.crystal();
+        assert_eq!(crystal.space_group().unwrap().number, 5);
+        let operators = crystal.symmetry_operators();
+        assert_eq!(operators.len(), 4);
+        assert!((operators[2].rows[0][3] - 96.98 / 2.0).abs() < 0.01);
+        assert!((operators[2].rows[1][3] - 46.65 / 2.0).abs() < 0.01);
+
+        let cell = pdb.unit_cell().unwrap();
+        assert_eq!(cell.models().count(), 4);
+        for model in cell.models() {
+            let atoms = model.coordinates().all().count();
+            assert_eq!(atoms, pdb.coordinates().all().count());
+        }
+
+        let mates = pdb.symmetry_mates(4.0);
+        assert_eq!(mates.len(), 12);
+        assert!(mates
+            .iter()
+            .any(|m| m.operator == 0 && m.translation == [0, 1, 0]));
+        assert!(!mates
+            .iter()
+            .any(|m| m.operator == 0 && m.translation == [0, 0, 0]));
+    }
+}
@@ -0,0 +1,80 @@
+/*!
+Contains parsers related to [Ter](http://www.wwpdb.org/documentation/file-format-content/format33/sect9.html#TER)
+records. The TER record indicates the end of a list of ATOM/HETATM records for a chain.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{character::complete::line_ending, do_parse, named};
+use std::convert::TryFrom;
+
+/// residue of the last atom of the chain. Available only if residue name
+/// and sequence number are given
+fn terminal_residue(rest: &[u8]) -> Option<ResidueReference> {
+    Some(ResidueReference {
+        residue_name: optional_column(rest, 14, 17)?.to_owned(),
+        chain_id: optional_column(rest, 18, 19)
+            .and_then(|c| c.chars().next())
+            .unwrap_or(' '),
+        sequence_number: i32::try_from(hybrid36_decode(4, optional_column(rest, 19, 23)?)?).ok()?,
+        insertion_code: optional_column(rest, 23, 24).and_then(|c| c.chars().next()),
+    })
+}
+
+named!(
+    #[doc=r#"Parses a single TER record. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing [Ter](../ast/types/struct.Ter.html) instance. Fields following the record
+name are frequently omitted, so all of them are optional.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD      | DEFINITION                    |
+|----------|--------------|------------|-------------------------------|
+| 1 -  6   | Record name  | "TER   "   |                               |
+| 7 - 11   | Integer      | serial     | Serial number.                |
+| 18 - 20  | Residue name | resName    | Residue name.                 |
+| 22       | Character    | chainID    | Chain identifier.             |
+| 23 - 26  | Integer      | resSeq     | Residue sequence number.      |
+| 27       | AChar        | iCode      | Insertion code.               |
+"#],
+    pub ter_record_parser<Record>,
+    do_parse!(
+        ter >> rest: till_line_ending
+            >> line_ending
+            >> (Record::Ter(Ter {
+                serial: optional_column(rest, 3, 8)
+                    .and_then(|s| hybrid36_decode(5, s))
+                    .and_then(|v| u32::try_from(v).ok()),
+                residue: terminal_residue(rest),
+            }))
+    )
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ter() {
+        if let Ok((_, Record::Ter(res))) = ter_record_parser(
+            "TER    4785      ALA A 323                                                      \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.serial, Some(4785));
+            let residue = res.residue.unwrap();
+            assert_eq!(residue.residue_name, "ALA");
+            assert_eq!(residue.chain_id, 'A');
+            assert_eq!(residue.sequence_number, 323);
+            assert_eq!(residue.insertion_code, None);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn bare_ter() {
+        if let Ok((_, Record::Ter(res))) = ter_record_parser("TER\n".as_bytes()) {
+            assert_eq!(res, Ter::default());
+        } else {
+            panic!()
+        }
+    }
+}
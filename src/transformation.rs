@@ -0,0 +1,211 @@
+/*!
+Contains parsers related to coordinate transformation records [OrigxN](http://www.wwpdb.org/documentation/file-format-content/format33/sect8.html#ORIGXn),
+[ScaleN](http://www.wwpdb.org/documentation/file-format-content/format33/sect8.html#SCALEn) and
+[MtrixN](http://www.wwpdb.org/documentation/file-format-content/format33/sect8.html#MTRIXn).
+Each of them gives one row of a 3x4 transformation matrix, three consecutive records
+are gathered into a single [Matrix3x4](../ast/types/struct.Matrix3x4.html).
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{
+    character::complete::line_ending, do_parse, error::ErrorKind, map_opt, named, take, Err,
+    IResult,
+};
+
+#[allow(dead_code)]
+pub struct TransformationLine {
+    row: usize,
+    serial: Option<u32>,
+    values: [f64; 4],
+    given: bool,
+}
+
+named!(
+    row_number<usize>,
+    map_opt!(take!(1), |c: &[u8]| match c[0] {
+        b'1'..=b'3' => Some(usize::from(c[0] - b'0')),
+        _ => None,
+    })
+);
+
+named!(
+    transformation_values<[f64; 4]>,
+    do_parse!(
+        m1: tendigit_real
+            >> m2: tendigit_real
+            >> m3: tendigit_real
+            >> take!(5)
+            >> v: tendigit_real
+            >> ([m1, m2, m3, v])
+    )
+);
+
+named!(#[doc=r#"Parses a line of ORIGXn record.
+
+| COLUMNS  | DATA  TYPE   | FIELD       | DEFINITION                                         |
+|----------|--------------|-------------|----------------------------------------------------|
+| 1 -  6   | Record name  | "ORIGXn"    | n=1, 2, or 3                                       |
+| 11 - 20  | Real(10.6)   | o[n][1]     | On1                                                |
+| 21 - 30  | Real(10.6)   | o[n][2]     | On2                                                |
+| 31 - 40  | Real(10.6)   | o[n][3]     | On3                                                |
+| 46 - 55  | Real(10.5)   | t[n]        | Tn                                                 |
+"#],
+    pub origx_line_parser<TransformationLine>,
+    do_parse!(
+        origx
+            >> row: row_number
+            >> take!(4)
+            >> values: transformation_values
+            >> till_line_ending
+            >> line_ending
+            >> (TransformationLine { row, serial: None, values, given: false })
+    )
+);
+
+named!(#[doc=r#"Parses a line of SCALEn record.
+
+| COLUMNS  | DATA  TYPE   | FIELD       | DEFINITION                                         |
+|----------|--------------|-------------|----------------------------------------------------|
+| 1 -  6   | Record name  | "SCALEn"    | n=1,  2, or  3                                     |
+| 11 - 20  | Real(10.6)   | s[n][1]     | Sn1                                                |
+| 21 - 30  | Real(10.6)   | s[n][2]     | Sn2                                                |
+| 31 - 40  | Real(10.6)   | s[n][3]     | Sn3                                                |
+| 46 - 55  | Real(10.5)   | u[n]        | Un                                                 |
+"#],
+    pub scale_line_parser<TransformationLine>,
+    do_parse!(
+        scale
+            >> row: row_number
+            >> take!(4)
+            >> values: transformation_values
+            >> till_line_ending
+            >> line_ending
+            >> (TransformationLine { row, serial: None, values, given: false })
+    )
+);
+
+named!(#[doc=r#"Parses a line of MTRIXn record.
+
+| COLUMNS  | DATA  TYPE   | FIELD       | DEFINITION                                         |
+|----------|--------------|-------------|----------------------------------------------------|
+| 1 -  6   | Record name  | "MTRIXn"    | n=1, 2, or 3                                       |
+| 8 - 10   | Integer      | serial      | Serial number.                                     |
+| 11 - 20  | Real(10.6)   | m[n][1]     | Mn1                                                |
+| 21 - 30  | Real(10.6)   | m[n][2]     | Mn2                                                |
+| 31 - 40  | Real(10.6)   | m[n][3]     | Mn3                                                |
+| 46 - 55  | Real(10.5)   | v[n]        | Vn                                                 |
+| 60       | Integer      | iGiven      | 1 if coordinates for the representations which are |
+|          |              |             | approximately related by the transformations of    |
+|          |              |             | the molecule are contained in the entry.           |
+|          |              |             | Otherwise, blank.                                  |
+"#],
+    pub mtrix_line_parser<TransformationLine>,
+    do_parse!(
+        mtrix
+            >> row: row_number
+            >> take!(1)
+            >> serial: threedigit_integer
+            >> values: transformation_values
+            >> rest: till_line_ending
+            >> line_ending
+            >> (TransformationLine {
+                row,
+                serial: Some(serial),
+                values,
+                given: optional_column(rest, 4, 5) == Some("1"),
+            })
+    )
+);
+
+/// gathers rows 1, 2 and 3 given in consecutive lines. Fails at the row number if a row
+/// is missing or at the serial number if the serial numbers of the rows differ
+fn transformation_parser(
+    line_parser: fn(&[u8]) -> IResult<&[u8], TransformationLine>,
+    s: &[u8],
+) -> IResult<&[u8], (Matrix3x4, TransformationLine)> {
+    let (mut rest, first) = line_parser(s)?;
+    if first.row != 1 {
+        return Err(Err::Error((&s[5..], ErrorKind::Verify)));
+    }
+    let mut matrix = Matrix3x4::identity();
+    matrix.rows[0] = first.values;
+    for row in 2..=3 {
+        let (next, line) = line_parser(rest)?;
+        if line.row != row {
+            return Err(Err::Error((&rest[5..], ErrorKind::Verify)));
+        }
+        if line.serial != first.serial {
+            return Err(Err::Error((&rest[7..], ErrorKind::Verify)));
+        }
+        matrix.rows[row - 1] = line.values;
+        rest = next;
+    }
+    Ok((rest, (matrix, first)))
+}
+
+/// Parses ORIGX1, ORIGX2 and ORIGX3 lines. If successfull returns
+/// [Record](../ast/types/enum.Record.html) variant containing the transformation from
+/// orthogonal coordinates to the submitted coordinates.
+pub fn origx_record_parser(s: &[u8]) -> IResult<&[u8], Record> {
+    let (rest, (matrix, _)) = transformation_parser(origx_line_parser, s)?;
+    Ok((rest, Record::Origx(matrix)))
+}
+
+/// Parses SCALE1, SCALE2 and SCALE3 lines. If successfull returns
+/// [Record](../ast/types/enum.Record.html) variant containing the transformation from
+/// orthogonal coordinates to fractional crystallographic coordinates.
+pub fn scale_record_parser(s: &[u8]) -> IResult<&[u8], Record> {
+    let (rest, (matrix, _)) = transformation_parser(scale_line_parser, s)?;
+    Ok((rest, Record::Scale(matrix)))
+}
+
+/// Parses MTRIX1, MTRIX2 and MTRIX3 lines of a single operator. If successfull returns
+/// [Record](../ast/types/enum.Record.html) variant containing [Mtrix](../ast/types/struct.Mtrix.html) instance.
+pub fn mtrix_record_parser(s: &[u8]) -> IResult<&[u8], Record> {
+    let (rest, (matrix, first)) = transformation_parser(mtrix_line_parser, s)?;
+    Ok((
+        rest,
+        Record::Mtrix(Mtrix {
+            serial: first.serial.unwrap_or_default(),
+            matrix,
+            given: first.given,
+        }),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scale() {
+        let lines = r#"SCALE1      0.010311  0.000000  0.004934        0.00000                         
+SCALE2      0.000000  0.021436  0.000000        0.00000                         
+SCALE3      0.000000  0.000000  0.016871        0.00000                         
+"#;
+        if let Ok((rest, Record::Scale(res))) = scale_record_parser(lines.as_bytes()) {
+            assert!(rest.is_empty());
+            assert_eq!(res.rows[0], [0.010311, 0.0, 0.004934, 0.0]);
+            assert_eq!(res.rows[2][2], 0.016871);
+        } else {
+            panic!()
+        }
+        assert!(scale_record_parser(&lines.as_bytes()[81..]).is_err());
+    }
+
+    #[test]
+    fn mtrix() {
+        let lines = r#"MTRIX1   1 -0.500000  0.866025  0.000000       10.00000    1                    
+MTRIX2   1 -0.866025 -0.500000  0.000000        0.00000    1                    
+MTRIX3   1  0.000000  0.000000  1.000000        0.00000    1                    
+MTRIX1   2  1.000000  0.000000  0.000000        0.00000                         
+"#;
+        if let Ok((rest, Record::Mtrix(res))) = mtrix_record_parser(lines.as_bytes()) {
+            assert_eq!(res.serial, 1);
+            assert!(res.given);
+            assert_eq!(res.matrix.rows[0], [-0.5, 0.866025, 0.0, 10.0]);
+            assert!(rest.starts_with(b"MTRIX1   2"));
+        } else {
+            panic!()
+        }
+    }
+}
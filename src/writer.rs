@@ -0,0 +1,1014 @@
+/*!
+Contains functions that serialize parsed records back to the 80 column
+[PDB format](http://www.wwpdb.org/documentation/file-format-content/format33/v3.3.html).
+Continuation records such as TITLE, COMPND, SOURCE, KEYWDS, AUTHOR and JRNL are wrapped
+at word or list boundaries and numbered the way the record parsers expect them.
+*/
+use super::{ast::types::*, error::PdbWriteError, primitive::hybrid36_encode};
+use chrono::NaiveDate;
+
+/// formats a date in DD-MMM-YY format
+pub fn format_date(date: &NaiveDate) -> String {
+    date.format("%d-%b-%y").to_string().to_uppercase()
+}
+
+fn format_char(c: Option<char>) -> char {
+    c.unwrap_or(' ')
+}
+
+fn format_charge(charge: Option<i8>) -> String {
+    match charge {
+        Some(c) if c > 0 => format!("{}+", c),
+        Some(c) if c < 0 => format!("{}-", -c),
+        _ => String::new(),
+    }
+}
+
+/// atom names start at column 13 if they are 4 characters long or if their element
+/// symbol has two letters, otherwise at column 14
+fn format_atom_name(name: &str, element: Option<&String>) -> String {
+    let two_letter_element = element.map_or(name.starts_with(char::is_numeric), |e| e.len() == 2);
+    if name.len() >= 4 || two_letter_element {
+        format!("{:<4}", name)
+    } else {
+        format!(" {:<3}", name)
+    }
+}
+
+/// splits `text` into chunks fitting `first_width` columns on the first line and
+/// `width` columns on the following ones. Lines are broken at spaces, which are
+/// dropped, or after commas. Words longer than a line are split.
+fn wrap(text: &str, first_width: usize, width: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut remaining = text.trim();
+    while !remaining.is_empty() {
+        let line_width = if chunks.is_empty() {
+            first_width
+        } else {
+            width
+        };
+        let chars: Vec<(usize, char)> = remaining.char_indices().collect();
+        if chars.len() <= line_width {
+            chunks.push(remaining.to_owned());
+            break;
+        }
+        let split = (1..=line_width)
+            .rev()
+            .find(|&i| chars[i].1 == ' ' || chars[i - 1].1 == ',')
+            .unwrap_or(line_width);
+        let split = chars[split].0;
+        chunks.push(remaining[..split].trim_end().to_owned());
+        remaining = remaining[split..].trim_start();
+    }
+    chunks
+}
+
+/// builds lines of a continuation record. Every paragraph starts on a new line.
+/// `prefix` holds the columns before the continuation field, `separator` the columns
+/// between the continuation field and the text of continued lines
+fn continued_lines(
+    prefix: &str,
+    continuation_width: usize,
+    first_separator: &str,
+    separator: &str,
+    last_column: usize,
+    paragraphs: &[String],
+) -> Vec<String> {
+    let text_start = prefix.len() + continuation_width;
+    let mut lines: Vec<String> = Vec::new();
+    for paragraph in paragraphs {
+        let first_width = if lines.is_empty() {
+            last_column - text_start - first_separator.len()
+        } else {
+            last_column - text_start - separator.len()
+        };
+        let width = last_column - text_start - separator.len();
+        for chunk in wrap(paragraph, first_width, width) {
+            if lines.is_empty() {
+                lines.push(format!(
+                    "{}{}{}{}",
+                    prefix,
+                    " ".repeat(continuation_width),
+                    first_separator,
+                    chunk
+                ));
+            } else {
+                lines.push(format!(
+                    "{}{:>width$}{}{}",
+                    prefix,
+                    lines.len() + 1,
+                    separator,
+                    chunk,
+                    width = continuation_width
+                ));
+            }
+        }
+    }
+    lines
+}
+
+/// lines of the title section records having the continuation field at columns 9 - 10
+fn title_section_lines(record_name: &str, last_column: usize, text: &str) -> Vec<String> {
+    continued_lines(
+        &format!("{:<8}", record_name),
+        2,
+        "",
+        " ",
+        last_column,
+        &[text.to_owned()],
+    )
+}
+
+/// lines of JRNL sub records having the continuation field at columns 17 - 18
+fn journal_lines(sub_record: &str, text: &str) -> Vec<String> {
+    continued_lines(
+        &format!("JRNL        {:<4}", sub_record),
+        2,
+        " ",
+        " ",
+        79,
+        &[text.to_owned()],
+    )
+}
+
+/// lines of records listing id codes after a date and the id code of the entry
+fn id_code_lines(
+    record_name: &str,
+    date: &NaiveDate,
+    id_code: &str,
+    ids: &[String],
+) -> Vec<String> {
+    let mut chunks = ids.chunks(8);
+    let mut lines = vec![format!(
+        "{:<6}    {} {:<4}      {}",
+        record_name,
+        format_date(date),
+        id_code,
+        chunks.next().map(|c| c.join(" ")).unwrap_or_default()
+    )];
+    for chunk in chunks {
+        lines.push(format!(
+            "{:<6}  {:>2} {} {:<4}      {}",
+            record_name,
+            lines.len() + 1,
+            format_date(date),
+            id_code,
+            chunk.join(" ")
+        ));
+    }
+    lines
+}
+
+fn token_text(token: &Token) -> String {
+    let list = |values: &[String]| values.join(", ");
+    let numbers = |values: &[u32]| {
+        values
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let yes_no = |value: bool| if value { "YES" } else { "NO" };
+    match token {
+        Token::MoleculeId(v) => format!("MOL_ID: {}", v),
+        Token::Molecule(v) => format!("MOLECULE: {}", v),
+        Token::Chain { identifiers } => format!("CHAIN: {}", list(identifiers)),
+        Token::Fragment(v) => format!("FRAGMENT: {}", v),
+        Token::Synonym { synonyms } => format!("SYNONYM: {}", list(synonyms)),
+        Token::Ec { commission_numbers } => format!("EC: {}", list(commission_numbers)),
+        Token::Engineered(v) => format!("ENGINEERED: {}", yes_no(*v)),
+        Token::Mutation(v) => format!("MUTATION: {}", yes_no(*v)),
+        Token::OtherDetails(v) => format!("OTHER_DETAILS: {}", v),
+        Token::Synthetic(v) => format!("SYNTHETIC: {}", v),
+        Token::OrganismScientific(v) => format!("ORGANISM_SCIENTIFIC: {}", v),
+        Token::OrganismCommon { organisms } => format!("ORGANISM_COMMON: {}", list(organisms)),
+        Token::OrganismTaxId { id } => format!("ORGANISM_TAXID: {}", numbers(id)),
+        Token::Strain(v) => format!("STRAIN: {}", v),
+        Token::Variant(v) => format!("VARIANT: {}", v),
+        Token::CellLine(v) => format!("CELL_LINE: {}", v),
+        Token::Atcc(v) => format!("ATCC: {}", v),
+        Token::Organ(v) => format!("ORGAN: {}", v),
+        Token::Tissue(v) => format!("TISSUE: {}", v),
+        Token::Cell(v) => format!("CELL: {}", v),
+        Token::Organelle(v) => format!("ORGANELLE: {}", v),
+        Token::Secretion(v) => format!("SECRETION: {}", v),
+        Token::CellularLocation(v) => format!("CELLULAR_LOCATION: {}", v),
+        Token::Plasmid(v) => format!("PLASMID: {}", v),
+        Token::Gene { gene } => format!("GENE: {}", list(gene)),
+        Token::ExpressionSystem(v) => format!("EXPRESSION_SYSTEM: {}", v),
+        Token::ExpressionSystemCommon { systems } => {
+            format!("EXPRESSION_SYSTEM_COMMON: {}", list(systems))
+        }
+        Token::ExpressionSystemTaxId { id } => {
+            format!("EXPRESSION_SYSTEM_TAXID: {}", numbers(id))
+        }
+        Token::ExpressionSystemStrain(v) => format!("EXPRESSION_SYSTEM_STRAIN: {}", v),
+        Token::ExpressionSystemVariant(v) => format!("EXPRESSION_SYSTEM_VARIANT: {}", v),
+        Token::ExpressionSystemCellLine(v) => format!("EXPRESSION_SYSTEM_CELL_LINE: {}", v),
+        Token::ExpressionSystemAtcc(v) => format!("EXPRESSION_SYSTEM_ATCC_NUMBER: {}", v),
+        Token::ExpressionSystemOrgan(v) => format!("EXPRESSION_SYSTEM_ORGAN: {}", v),
+        Token::ExpressionSystemTissue(v) => format!("EXPRESSION_SYSTEM_TISSUE: {}", v),
+        Token::ExpressionSystemCell(v) => format!("EXPRESSION_SYSTEM_CELL: {}", v),
+        Token::ExpressionSystemOrganelle(v) => format!("EXPRESSION_SYSTEM_ORGANELLE: {}", v),
+        Token::ExpressionSystemCellularLocation(v) => {
+            format!("EXPRESSION_SYSTEM_CELLULAR_LOCATION: {}", v)
+        }
+        Token::ExpressionSystemVectorType(v) => format!("EXPRESSION_SYSTEM_VECTOR_TYPE: {}", v),
+        Token::ExpressionSystemVector(v) => format!("EXPRESSION_SYSTEM_VECTOR: {}", v),
+        Token::ExpressionSystemPlasmid(v) => format!("EXPRESSION_SYSTEM_PLASMID: {}", v),
+        Token::ExpressionSystemGene(v) => format!("EXPRESSION_SYSTEM_GENE: {}", v),
+    }
+}
+
+/// COMPND and SOURCE lines. Every token starts on a new line and
+/// all tokens except the last one are terminated with `;`
+fn token_lines(record_name: &str, tokens: &[Token]) -> Vec<String> {
+    let paragraphs = tokens
+        .iter()
+        .enumerate()
+        .map(|(i, t)| {
+            if i + 1 < tokens.len() {
+                token_text(t) + ";"
+            } else {
+                token_text(t)
+            }
+        })
+        .collect::<Vec<_>>();
+    continued_lines(&format!("{:<7}", record_name), 3, "", " ", 80, &paragraphs)
+}
+
+fn revdat_lines(revdat: &Revdat) -> Vec<String> {
+    let modification_type = match revdat.modification_type {
+        ModificationType::InitialRelease => 0,
+        _ => 1,
+    };
+    let mut chunks = revdat.modification_detail.chunks(4);
+    let details = |chunk: Option<&[String]>| {
+        chunk
+            .unwrap_or_default()
+            .iter()
+            .map(|d| format!("{:<6}", d))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let mut lines = vec![format!(
+        "REVDAT {:>3}   {} {:<4}    {}       {}",
+        revdat.modification_number,
+        format_date(&revdat.modification_date),
+        revdat.idcode,
+        modification_type,
+        details(chunks.next())
+    )];
+    for chunk in chunks {
+        lines.push(format!(
+            "REVDAT {:>3}{:>2}                           {}",
+            revdat.modification_number,
+            lines.len() + 1,
+            details(Some(chunk))
+        ));
+    }
+    lines
+}
+
+fn journal_reference_lines(reference: &JournalReference) -> Vec<String> {
+    let optional = |v: Option<u32>| v.map(|v| v.to_string()).unwrap_or_default();
+    let mut names = wrap(&reference.publication_name, 28, 28).into_iter();
+    let mut lines = vec![format!(
+        "JRNL        REF    {:<28}  {}{:>4} {:>5} {:>4}",
+        names.next().unwrap_or_default(),
+        if reference.volume.is_some() {
+            "V."
+        } else {
+            "  "
+        },
+        optional(reference.volume),
+        optional(reference.page),
+        optional(reference.year)
+    )];
+    for name in names {
+        lines.push(format!("JRNL        REF {:>2} {}", lines.len() + 1, name));
+    }
+    lines
+}
+
+/// HETNAM and HETSYN lines. Continued lines start one column later than the first one
+fn het_lines(record_name: &str, het_id: &str, text: &str) -> Vec<String> {
+    continued_lines(
+        &format!("{}  ", record_name),
+        2,
+        &format!(" {:>3} ", het_id),
+        &format!(" {:>3}  ", het_id),
+        70,
+        &[text.to_owned()],
+    )
+}
+
+/// `formatted` value of `field` right aligned in `width` columns.
+/// Fails if the value needs more columns
+fn format_columns(
+    field: &'static str,
+    formatted: String,
+    width: usize,
+) -> Result<String, PdbWriteError> {
+    if formatted.chars().count() > width {
+        return Err(PdbWriteError {
+            field,
+            value: formatted,
+            width,
+        });
+    }
+    Ok(format!("{:>width$}", formatted, width = width))
+}
+
+/// [hybrid-36](../primitive/fn.hybrid36_encode.html) number right aligned in `width` columns.
+/// Fails for values beyond the range of the encoding
+fn format_hybrid36<T: Into<i64>>(
+    field: &'static str,
+    value: T,
+    width: usize,
+) -> Result<String, PdbWriteError> {
+    let value = value.into();
+    let encoded = hybrid36_encode(width, value).ok_or_else(|| PdbWriteError {
+        field,
+        value: value.to_string(),
+        width,
+    })?;
+    Ok(format!("{:>width$}", encoded, width = width))
+}
+
+/// residue reference with the chain identifier directly preceding the sequence number
+fn format_residue(residue: &ResidueReference) -> Result<String, PdbWriteError> {
+    Ok(format!(
+        "{:>3} {}{}{}",
+        residue.residue_name,
+        residue.chain_id,
+        format_hybrid36("resSeq", residue.sequence_number, 4)?,
+        format_char(residue.insertion_code)
+    ))
+}
+
+/// residue reference with a blank column between chain identifier and sequence number
+fn format_spaced_residue(residue: &ResidueReference) -> Result<String, PdbWriteError> {
+    Ok(format!(
+        "{:>3} {} {}{}",
+        residue.residue_name,
+        residue.chain_id,
+        format_hybrid36("resSeq", residue.sequence_number, 4)?,
+        format_char(residue.insertion_code)
+    ))
+}
+
+/// atom name of an atom reference. Names shorter than 4 characters start at the
+/// second column, except for single atom ions such as `MN` whose element symbol has two letters
+fn format_reference_atom_name(atom: &AtomReference) -> String {
+    if atom.name.len() < 4 && atom.name != atom.residue_name {
+        format!(" {:<3}", atom.name)
+    } else {
+        format!("{:<4}", atom.name)
+    }
+}
+
+/// atom reference without alternate location indicator as in SHEET records
+fn format_atom_reference(atom: &AtomReference) -> Result<String, PdbWriteError> {
+    Ok(format!(
+        "{}{:>3} {}{}{}",
+        format_reference_atom_name(atom),
+        atom.residue_name,
+        atom.chain_id,
+        format_hybrid36("resSeq", atom.sequence_number, 4)?,
+        format_char(atom.insertion_code)
+    ))
+}
+
+/// atom reference with alternate location indicator as in LINK records
+fn format_located_atom_reference(atom: &AtomReference) -> Result<String, PdbWriteError> {
+    Ok(format!(
+        "{}{}{:>3} {}{}{}",
+        format_reference_atom_name(atom),
+        format_char(atom.alt_loc),
+        atom.residue_name,
+        atom.chain_id,
+        format_hybrid36("resSeq", atom.sequence_number, 4)?,
+        format_char(atom.insertion_code)
+    ))
+}
+
+/// symmetry operators and bond length columns shared by SSBOND and LINK records
+fn format_symmetry_and_length(
+    symmetry1: Option<SymmetryOperator>,
+    symmetry2: Option<SymmetryOperator>,
+    length: Option<f64>,
+) -> String {
+    format!(
+        "{:>6} {:>6} {:>5}",
+        symmetry1.map(|s| s.to_string()).unwrap_or_default(),
+        symmetry2.map(|s| s.to_string()).unwrap_or_default(),
+        length.map(|l| format!("{:.2}", l)).unwrap_or_default()
+    )
+}
+
+/// SITE lines listing four residues per line
+fn site_lines(site: &Site) -> Result<Vec<String>, PdbWriteError> {
+    let mut chunks = site.residues.chunks(4).collect::<Vec<_>>();
+    if chunks.is_empty() {
+        chunks.push(&[]);
+    }
+    chunks
+        .iter()
+        .enumerate()
+        .map(|(i, residues)| {
+            Ok(format!(
+                "SITE   {:>3} {:>3} {:>2} {}",
+                i + 1,
+                site.site_id,
+                site.num_res,
+                residues
+                    .iter()
+                    .map(format_residue)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join(" ")
+            ))
+        })
+        .collect()
+}
+
+/// ORIGXn, SCALEn and MTRIXn lines. `serial` fills columns 7 - 10 and `given` the
+/// iGiven column of MTRIXn records
+fn transformation_lines(
+    record_name: &str,
+    serial: &str,
+    matrix: &Matrix3x4,
+    given: &str,
+) -> Vec<String> {
+    matrix
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            format!(
+                "{}{}{:<4}{:>10.6}{:>10.6}{:>10.6}     {:>10.5}    {}",
+                record_name,
+                i + 1,
+                serial,
+                r[0],
+                r[1],
+                r[2],
+                r[3],
+                given
+            )
+        })
+        .collect()
+}
+
+fn seqres_lines(seqres: &Seqres) -> Vec<String> {
+    seqres
+        .residues
+        .chunks(13)
+        .enumerate()
+        .map(|(i, residues)| {
+            format!(
+                "SEQRES {:>3} {} {:>4}  {}",
+                i + 1,
+                format_char(seqres.chain_id),
+                seqres.num_res,
+                residues
+                    .iter()
+                    .map(|r| format!("{:>3}", r))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )
+        })
+        .collect()
+}
+
+fn atom_line(record_name: &str, atom: &Atom) -> Result<String, PdbWriteError> {
+    Ok(format!(
+        "{:<6}{} {}{}{:>3} {}{}{}   {}{}{}{}{}          {:>2}{:<2}",
+        record_name,
+        format_hybrid36("serial", atom.serial, 5)?,
+        format_atom_name(&atom.name, atom.element.as_ref()),
+        format_char(atom.alt_loc),
+        atom.residue_name,
+        atom.chain_id,
+        format_hybrid36("resSeq", atom.sequence_number, 4)?,
+        format_char(atom.insertion_code),
+        format_columns("x", format!("{:.3}", atom.x), 8)?,
+        format_columns("y", format!("{:.3}", atom.y), 8)?,
+        format_columns("z", format!("{:.3}", atom.z), 8)?,
+        format_columns("occupancy", format!("{:.2}", atom.occupancy), 6)?,
+        format_columns("tempFactor", format!("{:.2}", atom.temp_factor), 6)?,
+        atom.element.as_deref().unwrap_or_default(),
+        format_charge(atom.charge)
+    ))
+}
+
+fn anisou_line(anisou: &Anisou) -> Result<String, PdbWriteError> {
+    Ok(format!(
+        "ANISOU{} {}{}{:>3} {}{}{} {}{}{}{}{}{}      {:>2}{:<2}",
+        format_hybrid36("serial", anisou.serial, 5)?,
+        format_atom_name(&anisou.name, anisou.element.as_ref()),
+        format_char(anisou.alt_loc),
+        anisou.residue_name,
+        anisou.chain_id,
+        format_hybrid36("resSeq", anisou.sequence_number, 4)?,
+        format_char(anisou.insertion_code),
+        format_columns("u[0][0]", anisou.u11.to_string(), 7)?,
+        format_columns("u[1][1]", anisou.u22.to_string(), 7)?,
+        format_columns("u[2][2]", anisou.u33.to_string(), 7)?,
+        format_columns("u[0][1]", anisou.u12.to_string(), 7)?,
+        format_columns("u[0][2]", anisou.u13.to_string(), 7)?,
+        format_columns("u[1][2]", anisou.u23.to_string(), 7)?,
+        anisou.element.as_deref().unwrap_or_default(),
+        format_charge(anisou.charge)
+    ))
+}
+
+fn ter_line(ter: &Ter) -> Result<String, PdbWriteError> {
+    let serial = match ter.serial {
+        Some(serial) => format_hybrid36("serial", serial, 5)?,
+        None => String::new(),
+    };
+    Ok(match &ter.residue {
+        Some(residue) => format!("TER   {:>5}      {}", serial, format_residue(residue)?),
+        None => format!("TER   {}", serial),
+    })
+}
+
+/// four bonded atoms per line
+fn conect_lines(conect: &Conect) -> Result<Vec<String>, PdbWriteError> {
+    let serial = format_hybrid36("serial", conect.serial, 5)?;
+    if conect.bonded.is_empty() {
+        return Ok(vec![format!("CONECT{}", serial)]);
+    }
+    conect
+        .bonded
+        .chunks(4)
+        .map(|bonded| {
+            bonded
+                .iter()
+                .try_fold(format!("CONECT{}", serial), |line, bonded| {
+                    Ok(line + &format_hybrid36("serial", *bonded, 5)?)
+                })
+        })
+        .collect()
+}
+
+/// Serializes a record to its PDB lines. Lines are padded to 80 columns and
+/// do not contain line endings. `Record::Unknown` yields its original text.
+/// Fails if a value does not fit into its columns or a line exceeds 80 columns.
+pub fn record_lines(record: &Record) -> Result<Vec<String>, PdbWriteError> {
+    let lines = match record {
+        Record::Header(h) => vec![format!(
+            "HEADER    {:<40}{}   {:<4}",
+            h.classification,
+            format_date(&h.deposition_date),
+            h.id_code
+        )],
+        Record::Title(t) => title_section_lines("TITLE", 80, &t.title),
+        Record::Obslte(o) => match o.replacement_ids.split_first() {
+            Some((id_code, ids)) => id_code_lines("OBSLTE", &o.replacement_date, id_code, ids),
+            None => id_code_lines("OBSLTE", &o.replacement_date, "", &[]),
+        },
+        Record::Split(s) => continued_lines("SPLIT   ", 2, " ", " ", 80, &[s.id_codes.join(" ")]),
+        Record::Caveat(c) => continued_lines(
+            "CAVEAT  ",
+            2,
+            &format!(" {:<4}    ", c.id_code),
+            &format!(" {:<4}    ", c.id_code),
+            79,
+            std::slice::from_ref(&c.comment),
+        ),
+        Record::Sprsde(s) => id_code_lines("SPRSDE", &s.sprsde_date, &s.id_code, &s.superseeded),
+        Record::Seqres(s) => seqres_lines(s),
+        Record::Mdltyp(m) => title_section_lines("MDLTYP", 80, &m.structural_annotation.join(";")),
+        Record::Revdats(r) => r.revdat.iter().flat_map(revdat_lines).collect(),
+        Record::Cmpnd(c) => token_lines("COMPND", &c.tokens),
+        Record::Source(s) => token_lines("SOURCE", &s.tokens),
+        Record::Keywds(k) => title_section_lines("KEYWDS", 79, &k.keywords.join(", ")),
+        Record::JournalAuthors(a) => journal_lines(
+            "AUTH",
+            &a.authors
+                .iter()
+                .map(|a| a.0.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        Record::JournalTitle(t) => journal_lines("TITL", &t.title),
+        Record::JournalEditors(e) => journal_lines(
+            "EDIT",
+            &e.name
+                .iter()
+                .map(|a| a.0.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        Record::JournalReference(r) => journal_reference_lines(r),
+        Record::JournalCitation(c) => vec![format!(
+            "JRNL        REFN                   {:<4} {}",
+            match c.serial_type {
+                Some(SerialNumber::Issn) => "ISSN",
+                Some(SerialNumber::Essn) => "ESSN",
+                None => "",
+            },
+            c.serial.as_deref().unwrap_or_default()
+        )],
+        Record::JournalPublication(p) => journal_lines("PUBL", &p.publication),
+        Record::JournalPubMedId(p) => vec![format!("JRNL        PMID   {}", p.id)],
+        Record::JournalDoi(d) => vec![format!("JRNL        DOI    {}", d.id)],
+        Record::Experimental(e) => title_section_lines(
+            "EXPDTA",
+            79,
+            &e.techniques
+                .iter()
+                .map(ExperimentalTechnique::to_string)
+                .collect::<Vec<_>>()
+                .join("; "),
+        ),
+        Record::Nummdl(n) => vec![format!("NUMMDL    {:<4}", n.num)],
+        Record::Authors(a) => title_section_lines(
+            "AUTHOR",
+            79,
+            &a.authors
+                .iter()
+                .map(|a| a.0.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        Record::Dbref(d) => vec![format!(
+            "DBREF  {:<4} {} {:>4}{} {:>4}{} {:<6} {:<8} {:<12} {:>5}{} {:>5}{}",
+            d.idcode,
+            d.chain_id,
+            d.seq_begin,
+            format_char(d.initial_sequence),
+            d.seq_end,
+            format_char(d.ending_sequence),
+            d.database,
+            d.db_accession,
+            d.db_idcode,
+            d.db_seq_begin,
+            format_char(d.idbns_begin),
+            d.db_seq_end,
+            format_char(d.dbins_end)
+        )],
+        Record::Dbref1(d) => vec![format!(
+            "DBREF1 {:<4} {} {:>4}{} {:>4}{} {:<6}               {:<20}",
+            d.idcode,
+            d.chain_id,
+            d.seq_begin,
+            format_char(d.initial_sequence),
+            d.seq_end,
+            format_char(d.ending_sequence),
+            d.database,
+            d.db_idcode
+        )],
+        Record::Dbref2(d) => vec![format!(
+            "DBREF2 {:<4} {}     {:<22}     {:>10}  {:>10}",
+            d.idcode, d.chain_id, d.db_accession, d.db_seq_begin, d.db_seq_end
+        )],
+        Record::Seqadv(s) => vec![format!(
+            "SEQADV {:<4} {:>3} {} {}{} {:<4} {:<9} {:>3} {:>5} {}",
+            s.idcode,
+            s.conflicting_residue,
+            s.chain_id,
+            format_hybrid36("seqNum", s.sequence_number, 4)?,
+            format_char(s.insertion_code),
+            s.database,
+            s.db_accession,
+            s.sequence_db_residue.as_deref().unwrap_or_default(),
+            s.sequence_db_sequence_number
+                .map(|n| n.to_string())
+                .unwrap_or_default(),
+            s.conflict
+        )],
+        Record::Modres(m) => vec![format!(
+            "MODRES {:<4} {:>3} {} {}{} {:>3}  {}",
+            m.idcode,
+            m.residue_name,
+            m.chain_id,
+            format_hybrid36("seqNum", m.sequence_number, 4)?,
+            format_char(m.insertion_code),
+            m.standart_residue_name,
+            m.comment
+        )],
+        Record::Het(h) => vec![format!(
+            "HET    {:>3}  {}{}{}  {:>5}     {}",
+            h.het_id,
+            h.chain_id,
+            format_hybrid36("seqNum", h.sequence_number, 4)?,
+            format_char(h.insertion_code),
+            h.num_het_atoms,
+            h.text
+        )],
+        Record::Hetnam(h) => het_lines("HETNAM", &h.het_id, &h.name),
+        Record::Hetsyn(h) => het_lines("HETSYN", &h.het_id, &h.synonyms.join("; ")),
+        Record::Formul(f) => {
+            let asterisk = if f.water { "*" } else { " " };
+            continued_lines(
+                &format!("FORMUL  {:>2}  {:>3} ", f.component_number, f.het_id),
+                2,
+                asterisk,
+                asterisk,
+                70,
+                std::slice::from_ref(&f.formula),
+            )
+        }
+        Record::Helix(h) => vec![format!(
+            "HELIX  {:>3} {:>3} {} {}{:>2}{:<30} {:>5}",
+            h.serial,
+            h.helix_id,
+            format_spaced_residue(&h.start)?,
+            format_spaced_residue(&h.end)?,
+            h.class.number(),
+            h.comment,
+            h.length
+        )],
+        Record::Sheet(s) => vec![format!(
+            "SHEET  {:>3} {:>3}{:>2} {} {}{:>2}{}",
+            s.strand,
+            s.sheet_id,
+            s.num_strands,
+            format_residue(&s.start)?,
+            format_residue(&s.end)?,
+            match s.sense {
+                StrandSense::First => 0,
+                StrandSense::Parallel => 1,
+                StrandSense::AntiParallel => -1,
+            },
+            match &s.registration {
+                Some(r) => format!(
+                    " {} {}",
+                    format_atom_reference(&r.current)?,
+                    format_atom_reference(&r.previous)?
+                ),
+                None => String::new(),
+            }
+        )],
+        Record::Ssbond(b) => vec![format!(
+            "SSBOND {:>3} {}   {}{:23}{}",
+            b.serial,
+            format_spaced_residue(&b.residue1)?,
+            format_spaced_residue(&b.residue2)?,
+            "",
+            format_symmetry_and_length(b.symmetry1, b.symmetry2, b.length)
+        )],
+        Record::Link(l) => vec![format!(
+            "LINK        {}{:15}{}  {}",
+            format_located_atom_reference(&l.atom1)?,
+            "",
+            format_located_atom_reference(&l.atom2)?,
+            format_symmetry_and_length(l.symmetry1, l.symmetry2, l.length)
+        )],
+        Record::Cispep(c) => vec![format!(
+            "CISPEP {:>3} {}   {}{:7}{:>3}{:7}{:>6.2}",
+            c.serial,
+            format_spaced_residue(&c.residue1)?,
+            format_spaced_residue(&c.residue2)?,
+            "",
+            c.model,
+            "",
+            c.omega
+        )],
+        Record::Site(s) => site_lines(s)?,
+        Record::Cryst1(c) => vec![format!(
+            "CRYST1{:>9.3}{:>9.3}{:>9.3}{:>7.2}{:>7.2}{:>7.2} {:<11}{:>4}",
+            c.a, c.b, c.c, c.alpha, c.beta, c.gamma, c.space_group, c.z
+        )],
+        Record::Origx(m) => transformation_lines("ORIGX", "", m, ""),
+        Record::Scale(m) => transformation_lines("SCALE", "", m, ""),
+        Record::Mtrix(m) => transformation_lines(
+            "MTRIX",
+            &format!(" {:>3}", m.serial),
+            &m.matrix,
+            if m.given { "1" } else { "" },
+        ),
+        Record::Atom(a) => vec![atom_line("ATOM", a)?],
+        Record::Hetatm(a) => vec![atom_line("HETATM", a)?],
+        Record::Anisou(a) => vec![anisou_line(a)?],
+        Record::Ter(t) => vec![ter_line(t)?],
+        Record::Model(m) => vec![format!("MODEL     {:>4}", m.serial)],
+        Record::Endmdl => vec!["ENDMDL".to_owned()],
+        Record::Conect(c) => conect_lines(c)?,
+        Record::Master(m) => vec![format!(
+            "MASTER    {:>5}{:>5}{:>5}{:>5}{:>5}{:>5}{:>5}{:>5}{:>5}{:>5}{:>5}{:>5}",
+            m.num_remark,
+            0,
+            m.num_het,
+            m.num_helix,
+            m.num_sheet,
+            m.num_turn,
+            m.num_site,
+            m.num_xform,
+            m.num_coord,
+            m.num_ter,
+            m.num_conect,
+            m.num_seq
+        )],
+        Record::End => vec!["END".to_owned()],
+        Record::Remark(r) => r
+            .lines
+            .iter()
+            .map(|l| format!("REMARK {:>3} {}", r.number, l))
+            .collect(),
+        Record::Unknown { raw, .. } => return Ok(vec![raw.clone()]),
+    };
+    lines
+        .into_iter()
+        .map(|l| {
+            if l.chars().count() > 80 {
+                return Err(PdbWriteError {
+                    field: "line",
+                    value: l,
+                    width: 80,
+                });
+            }
+            Ok(format!("{:<80}", l))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse, ToPdbFile};
+
+    /// pads lines to 80 columns as in wwPDB distributed files before parsing
+    fn round_trip(text: &str) -> String {
+        let padded = text
+            .lines()
+            .map(|l| format!("{:<80}\n", l))
+            .collect::<String>();
+        let (_, pdb) = parse(&padded).unwrap();
+        pdb.to_pdb_string().unwrap()
+    }
+
+    #[test]
+    fn title_continuation() {
+        let title = Record::Title(Title {
+            title: "STRUCTURE OF APO-DETHIOBIOTIN SYNTHASE AT 0.97 ANGSTROMS RESOLUTION AND \
+                    SOME MORE WORDS TO FORCE A SECOND LINE"
+                .to_owned(),
+        });
+        let lines = record_lines(&title).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|l| l.len() == 80));
+        assert!(lines[0].starts_with("TITLE     STRUCTURE OF"));
+        assert!(lines[1].starts_with("TITLE    2 "));
+    }
+
+    #[test]
+    fn header_records() {
+        let text = r#"HEADER    LIGASE                                  15-OCT-98   1BYI
+TITLE     STRUCTURE OF APO-DETHIOBIOTIN SYNTHASE AT 0.97 ANGSTROMS RESOLUTION
+COMPND    MOL_ID: 1;
+COMPND   2 MOLECULE: DETHIOBIOTIN SYNTHASE;
+COMPND   3 CHAIN: A;
+COMPND   4 EC: 6.3.3.3
+SOURCE    MOL_ID: 1;
+SOURCE   2 ORGANISM_SCIENTIFIC: ESCHERICHIA COLI;
+SOURCE   3 ORGANISM_TAXID: 562
+KEYWDS    BIOTIN SYNTHESIS, CYCLO-LIGASE, LIGASE
+EXPDTA    X-RAY DIFFRACTION
+AUTHOR    T.SANDALOVA,G.SCHNEIDER,H.KAECK,Y.LINDQVIST
+REVDAT   3   13-JUL-11 1BYI    1       VERSN
+REVDAT   2   24-FEB-09 1BYI    1       VERSN
+REVDAT   1   15-JUN-99 1BYI    0
+JRNL        AUTH   T.SANDALOVA,G.SCHNEIDER,H.KACK,Y.LINDQVIST
+JRNL        TITL   STRUCTURE OF DETHIOBIOTIN SYNTHETASE AT 0.97 A RESOLUTION.
+JRNL        REF    ACTA CRYSTALLOGR.,SECT.D      V.  55   610 1999
+JRNL        REFN                   ISSN 0907-4449
+DBREF  1BYI A    1   224  UNP    P13000   BIOD_ECOLI       1    224
+ATOM      1  N   SER A   1      16.250  42.032  25.571  1.00 20.91           N
+ANISOU    1  N   SER A   1     1031   2118   1764   -123   -102    531       N
+HETATM 1849 ZN    ZN A 301      -5.012  21.230  -1.005  0.50 12.02          ZN2+
+"#;
+        let written = round_trip(text);
+        for (written, expected) in written.lines().zip(text.lines()) {
+            assert_eq!(written.len(), 80);
+            assert_eq!(written.trim_end(), expected);
+        }
+        assert_eq!(written.lines().count(), text.lines().count());
+    }
+
+    #[test]
+    fn hybrid36_numbers() {
+        let text = r#"ATOM  99999  N   GLY B9999      16.250  42.032  25.571  1.00 20.91           N
+ATOM  A0000  CA  GLY BA000      15.493  40.784  25.264  1.00 21.24           C
+ANISOUA0000  CA  GLY BA000     1031   2118   1764   -123   -102    531       C
+TER   A0001      GLY BA000
+HETATMA0002 ZN    ZN BA001      -5.012  21.230  -1.005  0.50 12.02          ZN2+
+CONECTA0002A0000"#;
+        let written = round_trip(text);
+        for (written, expected) in written.lines().zip(text.lines()) {
+            assert_eq!(written.trim_end(), expected);
+        }
+        let (_, pdb) = parse(&written).unwrap();
+        let serials = pdb
+            .coordinates()
+            .all()
+            .map(|a| a.serial)
+            .collect::<Vec<_>>();
+        assert_eq!(serials, vec![99_999, 100_000, 100_002]);
+        assert_eq!(pdb.connectivity().bonded_to(100_002), vec![100_000]);
+    }
+
+    #[test]
+    fn unencodable_numbers() {
+        let atom = Atom {
+            sequence_number: -1000,
+            ..Default::default()
+        };
+        assert_eq!(
+            record_lines(&Record::Atom(atom)),
+            Err(PdbWriteError {
+                field: "resSeq",
+                value: "-1000".to_owned(),
+                width: 4
+            })
+        );
+
+        let atom = Atom {
+            x: -1000.25,
+            ..Default::default()
+        };
+        assert_eq!(
+            record_lines(&Record::Atom(atom)),
+            Err(PdbWriteError {
+                field: "x",
+                value: "-1000.250".to_owned(),
+                width: 8
+            })
+        );
+        let atom = Atom {
+            temp_factor: 1000.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            record_lines(&Record::Atom(atom)).unwrap_err().field,
+            "tempFactor"
+        );
+        let anisou = Anisou {
+            u11: 10_000_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            record_lines(&Record::Anisou(anisou)).unwrap_err().field,
+            "u[0][0]"
+        );
+        let modres = Modres {
+            comment: "X".repeat(60),
+            ..Default::default()
+        };
+        assert_eq!(
+            record_lines(&Record::Modres(modres)).unwrap_err().field,
+            "line"
+        );
+
+        let pdb = vec![Record::Conect(Conect {
+            serial: 1,
+            bonded: vec![100_000_000],
+            ..Default::default()
+        })]
+        .to_pdb_file();
+        assert!(pdb.to_pdb_string().is_err());
+        let error = pdb.write(&mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn reparse() {
+        let text = r#"JRNL        AUTH   C.JELSCH,M.M.TEETER,V.LAMZIN,V.PICHON-PESME,R.H.BLESSING,
+JRNL        AUTH 2 C.LECOMTE
+MODRES 2R0L ASN A   74  ASN  GLYCOSYLATION SITE
+SEQADV 1NLS ASP A  151  UNP  P02866    GLU    62 CONFLICT
+MODEL        1
+ENDMDL
+"#;
+        let written = round_trip(text);
+        let (_, pdb) = parse(&written).unwrap();
+        assert_eq!(
+            pdb.header().journal().authors().unwrap().authors[5].0,
+            "C.LECOMTE"
+        );
+        assert_eq!(pdb.primary().conflicts().unwrap().sequence_number, 151);
+        assert_eq!(pdb.models().count(), 1);
+    }
+
+    #[test]
+    fn wrap_non_ascii() {
+        let title = "STRUCTURE OF THE \u{3b1}-SUBUNIT OF THE TRYPTOPHAN SYNTHASE FROM SALMONELLA TYPHIMURIUM";
+        let chunks = wrap(title, 60, 58);
+        assert_eq!(chunks.join(" "), title);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 60));
+
+        let unbroken = "\u{e9}".repeat(75);
+        let chunks = wrap(&unbroken, 70, 70);
+        assert_eq!(chunks[0].chars().count(), 70);
+        assert_eq!(chunks[1].chars().count(), 5);
+
+        let pdb = vec![Record::Title(Title {
+            title: title.to_owned(),
+        })]
+        .to_pdb_file();
+        assert_eq!(pdb.to_pdb_string().unwrap().lines().count(), 2);
+    }
+}